@@ -1,18 +1,80 @@
 #![no_std]
 use aidoku::{
-	alloc::{vec, String, Vec},
-	imports::{html::Document, net::Request},
+	alloc::{borrow::Cow, vec, Vec},
+	imports::net::Request,
 	prelude::*,
-	HomeComponent, HomeLayout, Link, LinkValue, Listing, ListingKind, Manga, MangaPageResult,
-	Result, Source,
+	Manga, Result, Source,
+};
+use gigaviewer::{
+	link_selectors, listing_entries, AuthedRequest, GigaViewer, HomeSection, HomeSectionKind, Impl,
+	ListSelectors, Params,
 };
-use gigaviewer::{GigaViewer, Impl, Params};
 
 const BASE_URL: &str = "https://comic-days.com";
 const CDN_URL: &str = "https://cdn-img.comic-days.com/public/page";
 
 struct ComicDays;
 
+fn home_sections() -> Vec<HomeSection> {
+	fn section(
+		title: &'static str,
+		kind: HomeSectionKind,
+		item_selector: &'static str,
+	) -> HomeSection {
+		HomeSection {
+			title: Some(title.into()),
+			subtitle: None,
+			subtitle_selector: None,
+			kind,
+			selectors: ListSelectors {
+				description: Some("p".into()),
+				..link_selectors(item_selector)
+			},
+			listing: None,
+		}
+	}
+
+	vec![
+		HomeSection {
+			title: None,
+			subtitle: None,
+			subtitle_selector: None,
+			kind: HomeSectionKind::Banner {
+				width: 341,
+				height: 128,
+			},
+			selectors: link_selectors(".swiper .swiper-slide:not(.swiper-slide-duplicate) a"),
+			listing: None,
+		},
+		section(
+			"新作＆話題作",
+			HomeSectionKind::Scroller,
+			".gtm-top-newtopic-item",
+		),
+		HomeSection {
+			subtitle_selector: Some("#ranking p".into()),
+			..section(
+				"今日のランキング",
+				HomeSectionKind::Ranking,
+				".gtm-top-ranking-オリジナル-item",
+			)
+		},
+		section(
+			"DAYSオリジナル",
+			HomeSectionKind::Scroller,
+			".gtm-top-days-original-item",
+		),
+		HomeSection {
+			listing: Some(("oneshot".into(), "読み切り".into())),
+			..section(
+				"新人作家・読み切り",
+				HomeSectionKind::Scroller,
+				".gtm-top-newcomer-item",
+			)
+		},
+	]
+}
+
 impl Impl for ComicDays {
 	fn new() -> Self {
 		Self
@@ -23,23 +85,19 @@ impl Impl for ComicDays {
 			base_url: BASE_URL.into(),
 			cdn_url: CDN_URL.into(),
 			is_paginated: true,
+			home_sections: Cow::Owned(home_sections()),
 			..Default::default()
 		}
 	}
 
-	fn get_manga_list(
-		&self,
-		_params: &Params,
-		listing: Listing,
-		_page: i32,
-	) -> Result<MangaPageResult> {
+	fn get_listing_entries(&self, params: &Params, id: &str) -> Result<Vec<Manga>> {
 		let item_selector: &str;
 		let title_selector: &str;
 		let cover_selector: &str;
 		let cover_attr: &str;
 		let authors_selector: Option<&str>;
 
-		match listing.id.as_str() {
+		match id {
 			"series" => {
 				item_selector = "ul.daily-series li.daily-series-item";
 				title_selector = ".daily-series-title";
@@ -54,144 +112,27 @@ impl Impl for ComicDays {
 				cover_attr = "src";
 				authors_selector = Some(".yomikiri-link-title h5");
 			}
-			_ => return Impl::get_manga_list(self, _params, listing, _page),
+			_ => return listing_entries(params, id),
 		}
 
-		let base_url = self.params().base_url;
-		let html = Request::get(format!("{}/{}", base_url, listing.id))?.html()?;
+		let html = Request::get(format!("{}/{id}", params.base_url))?.html_authed()?;
 
-		let entries = gigaviewer::parser::parse_response(
+		Ok(gigaviewer::parser::parse_response(
 			&html,
-			&base_url,
+			&params.base_url,
 			item_selector,
 			title_selector,
 			cover_selector,
 			cover_attr,
 			authors_selector,
 			None,
-		);
-
-		Ok(MangaPageResult {
-			entries,
-			has_next_page: false,
-		})
-	}
-
-	fn get_home(&self, _params: &Params) -> Result<HomeLayout> {
-		let html = Request::get(BASE_URL)?.html()?;
-
-		fn parse_home_section(html: &Document, item_selector: &str) -> Vec<Link> {
-			gigaviewer::parser::parse_response(
-				html,
-				BASE_URL,
-				item_selector,
-				"h3",
-				"img",
-				"src",
-				None,
-				Some("p"),
-			)
-			.into_iter()
-			.map(|manga| manga.into())
-			.collect()
-		}
-
-		// banner
-		let manga_prefix = format!("{BASE_URL}/episode");
-		let links = html
-			.select_first(".swiper")
-			.and_then(|x| {
-				Some(
-					x.select(".swiper-slide:not(.swiper-slide-duplicate) a")?
-						.filter_map(|e| {
-							let image = e.select_first("img").and_then(|x| x.attr("src"))?;
-							let url = e.attr("href")?;
-							let value = if url.starts_with(&manga_prefix) {
-								let key = url.strip_prefix(BASE_URL).map(String::from)?;
-								LinkValue::Manga(Manga {
-									key,
-									..Default::default()
-								})
-							} else {
-								LinkValue::Url(url)
-							};
-							let link = Link {
-								title: String::default(),
-								image_url: Some(image),
-								value: Some(value),
-								..Default::default()
-							};
-							Some(link)
-						})
-						.collect(),
-				)
-			})
-			.unwrap_or_default();
-
-		// sections
-		let ranking_subtitle = html.select_first("#ranking p").and_then(|x| x.text());
-		let new_topics = parse_home_section(&html, ".gtm-top-newtopic-item");
-		let ranking = parse_home_section(&html, ".gtm-top-ranking-オリジナル-item");
-		let originals = parse_home_section(&html, ".gtm-top-days-original-item");
-		let newcomers = parse_home_section(&html, ".gtm-top-newcomer-item");
-
-		Ok(HomeLayout {
-			components: vec![
-				HomeComponent {
-					title: None,
-					subtitle: None,
-					value: aidoku::HomeComponentValue::ImageScroller {
-						links,
-						auto_scroll_interval: Some(4.0),
-						width: Some(341),
-						height: Some(128),
-					},
-				},
-				HomeComponent {
-					title: Some("新作＆話題作".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::Scroller {
-						entries: new_topics,
-						listing: None,
-					},
-				},
-				HomeComponent {
-					title: Some("今日のランキング".into()),
-					subtitle: ranking_subtitle,
-					value: aidoku::HomeComponentValue::MangaList {
-						ranking: true,
-						page_size: Some(3),
-						entries: ranking,
-						listing: None,
-					},
-				},
-				HomeComponent {
-					title: Some("DAYSオリジナル".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::Scroller {
-						entries: originals,
-						listing: None,
-					},
-				},
-				HomeComponent {
-					title: Some("新人作家・読み切り".into()),
-					subtitle: None,
-					value: aidoku::HomeComponentValue::Scroller {
-						entries: newcomers,
-						listing: Some(Listing {
-							id: "oneshot".into(),
-							name: "読み切り".into(),
-							kind: ListingKind::List,
-						}),
-					},
-				},
-			],
-		})
+		))
 	}
 }
 
 register_source!(
 	GigaViewer<ComicDays>,
+	ListingProvider,
 	PageImageProcessor,
 	Home,
 	BasicLoginHandler,
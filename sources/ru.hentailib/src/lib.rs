@@ -1,6 +1,21 @@
 #![no_std]
 use aidoku::{Source, alloc::borrow::Cow, prelude::*};
-use libgroup::{Impl, LibGroup, Params};
+use libgroup::{Impl, LibGroup, Params, SiteListing};
+
+const SITE_LISTINGS: &[SiteListing] = &[
+	SiteListing {
+		id: "doujinshi",
+		name: "Додзинси",
+		query: &[("format[]", "3")],
+		on_home: true,
+	},
+	SiteListing {
+		id: "colored",
+		name: "В цвете",
+		query: &[("format[]", "4")],
+		on_home: true,
+	},
+];
 
 struct HentaiLib;
 
@@ -12,6 +27,9 @@ impl Impl for HentaiLib {
 	fn params(&self) -> Params {
 		Params {
 			site_id: Cow::Owned(4),
+			default_age_ratings: Cow::Borrowed(&["4", "5"]),
+			site_listings: Cow::Borrowed(SITE_LISTINGS),
+			domains: Cow::Borrowed(&["hentailib.me"]),
 		}
 	}
 }
@@ -19,8 +37,11 @@ impl Impl for HentaiLib {
 register_source!(
 	LibGroup<HentaiLib>,
 	ListingProvider,
+	DynamicListings,
 	Home,
 	ImageRequestProvider,
+	PageDescriptionProvider,
 	AlternateCoverProvider,
-	MigrationHandler
+	MigrationHandler,
+	DeepLinkHandler
 );
@@ -12,6 +12,9 @@ impl Impl for MangaLib {
 	fn params(&self) -> Params {
 		Params {
 			site_id: Cow::Owned(1),
+			default_age_ratings: Cow::Borrowed(&[]),
+			site_listings: Cow::Borrowed(&[]),
+			domains: Cow::Borrowed(&["mangalib.me", "api.mangalib.me", "api2.mangalib.me"]),
 		}
 	}
 }
@@ -19,8 +22,11 @@ impl Impl for MangaLib {
 register_source!(
 	LibGroup<MangaLib>,
 	ListingProvider,
+	DynamicListings,
 	Home,
 	ImageRequestProvider,
+	PageDescriptionProvider,
 	AlternateCoverProvider,
-	MigrationHandler
+	MigrationHandler,
+	DeepLinkHandler
 );
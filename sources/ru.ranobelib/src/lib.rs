@@ -1,6 +1,27 @@
 #![no_std]
 use aidoku::{Source, alloc::borrow::Cow, prelude::*};
-use libgroup::{Impl, LibGroup, Params};
+use libgroup::{Impl, LibGroup, Params, SiteListing};
+
+const SITE_LISTINGS: &[SiteListing] = &[
+	SiteListing {
+		id: "new_titles",
+		name: "Новинки",
+		query: &[("sort_by", "created_at")],
+		on_home: true,
+	},
+	SiteListing {
+		id: "original",
+		name: "Авторские",
+		query: &[("types[]", "14")],
+		on_home: true,
+	},
+	SiteListing {
+		id: "completed",
+		name: "Завершённые",
+		query: &[("status[]", "2"), ("sort_by", "rate_avg")],
+		on_home: false,
+	},
+];
 
 struct RanobeLib;
 
@@ -12,6 +33,9 @@ impl Impl for RanobeLib {
 	fn params(&self) -> Params {
 		Params {
 			site_id: Cow::Owned(3),
+			default_age_ratings: Cow::Borrowed(&[]),
+			site_listings: Cow::Borrowed(SITE_LISTINGS),
+			domains: Cow::Borrowed(&["ranobelib.me"]),
 		}
 	}
 }
@@ -19,8 +43,11 @@ impl Impl for RanobeLib {
 register_source!(
 	LibGroup<RanobeLib>,
 	ListingProvider,
+	DynamicListings,
 	Home,
 	ImageRequestProvider,
+	PageDescriptionProvider,
 	AlternateCoverProvider,
-	MigrationHandler
+	MigrationHandler,
+	DeepLinkHandler
 );
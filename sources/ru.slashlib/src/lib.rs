@@ -12,6 +12,9 @@ impl Impl for SlashLib {
 	fn params(&self) -> Params {
 		Params {
 			site_id: Cow::Owned(2),
+			default_age_ratings: Cow::Borrowed(&["3", "4", "5"]),
+			site_listings: Cow::Borrowed(&[]),
+			domains: Cow::Borrowed(&["slashlib.me", "v2.shlib.life"]),
 		}
 	}
 }
@@ -19,8 +22,11 @@ impl Impl for SlashLib {
 register_source!(
 	LibGroup<SlashLib>,
 	ListingProvider,
+	DynamicListings,
 	Home,
 	ImageRequestProvider,
+	PageDescriptionProvider,
 	AlternateCoverProvider,
-	MigrationHandler
+	MigrationHandler,
+	DeepLinkHandler
 );
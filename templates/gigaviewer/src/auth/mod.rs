@@ -0,0 +1,251 @@
+use aidoku::{
+	alloc::{String, Vec},
+	helpers::uri::encode_uri_component,
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		error::AidokuError,
+		html::{Document, Html},
+		net::{Request, Response},
+	},
+	prelude::*,
+	Result,
+};
+use serde::de::DeserializeOwned;
+
+static EMAIL_KEY: &str = "login.username";
+static PASSWORD_KEY: &str = "login.password";
+static COOKIE_KEY: &str = "login.cookie";
+
+/// Cookies of the logged in session, stored as a `Cookie` header value
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CookieJar {
+	cookies: Vec<(String, String)>,
+}
+
+impl CookieJar {
+	pub fn load() -> Self {
+		defaults_get::<String>(COOKIE_KEY)
+			.map(|header| Self::from_header(&header))
+			.unwrap_or_default()
+	}
+
+	pub fn save(&self) {
+		if self.cookies.is_empty() {
+			defaults_set(COOKIE_KEY, DefaultValue::Null);
+		} else {
+			defaults_set(COOKIE_KEY, DefaultValue::String(self.to_header()));
+		}
+	}
+
+	/// Parse a `Cookie` header value, ex: `glsc=abc; other=1`
+	pub fn from_header(header: &str) -> Self {
+		let mut jar = Self::default();
+		for pair in header.split(';') {
+			if let Some((name, value)) = pair.split_once('=') {
+				jar.set(name.trim(), value.trim());
+			}
+		}
+		jar
+	}
+
+	pub fn to_header(&self) -> String {
+		self.cookies
+			.iter()
+			.map(|(name, value)| format!("{name}={value}"))
+			.collect::<Vec<_>>()
+			.join("; ")
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.cookies.is_empty()
+	}
+
+	/// Apply the `Set-Cookie` header of a response sent with this session,
+	/// returns whether any cookie changed. Without a session there is nothing to renew.
+	pub fn renew(&mut self, set_cookie: Option<&str>) -> bool {
+		let Some(set_cookie) = set_cookie.filter(|_| !self.is_empty()) else {
+			return false;
+		};
+		let before = self.clone();
+		self.update(set_cookie);
+		*self != before
+	}
+
+	/// Apply a `Set-Cookie` header, which may hold several cookies joined by commas
+	pub fn update(&mut self, set_cookie: &str) {
+		for cookie in split_set_cookie(set_cookie) {
+			let mut attributes = cookie.split(';');
+			let Some((name, value)) = attributes.next().and_then(|pair| pair.split_once('='))
+			else {
+				continue;
+			};
+			let (name, value) = (name.trim(), value.trim());
+			let expired = attributes.any(|attribute| {
+				let attribute = attribute.trim().to_ascii_lowercase();
+				attribute == "max-age=0" || attribute.starts_with("max-age=-")
+			});
+			if expired || value.is_empty() {
+				self.cookies.retain(|(n, _)| n != name);
+			} else {
+				self.set(name, value);
+			}
+		}
+	}
+
+	fn set(&mut self, name: &str, value: &str) {
+		if name.is_empty() {
+			return;
+		}
+		if let Some(cookie) = self.cookies.iter_mut().find(|(n, _)| n == name) {
+			cookie.1 = value.into();
+		} else {
+			self.cookies.push((name.into(), value.into()));
+		}
+	}
+}
+
+/// Split a combined `Set-Cookie` header into cookies, keeping commas inside `Expires` dates
+fn split_set_cookie(header: &str) -> Vec<String> {
+	let mut cookies: Vec<String> = Vec::new();
+	for segment in header.split(',') {
+		// a new cookie starts with `name=` before any attribute
+		let starts_cookie = segment
+			.split(';')
+			.next()
+			.and_then(|pair| pair.split_once('='))
+			.is_some_and(|(name, _)| {
+				let name = name.trim();
+				!name.is_empty() && !name.contains(' ')
+			});
+		match cookies.last_mut() {
+			Some(last) if !starts_cookie => {
+				last.push(',');
+				last.push_str(segment);
+			}
+			_ => cookies.push(segment.trim().into()),
+		}
+	}
+	cookies
+}
+
+pub fn login(base_url: &str, email: &str, password: &str) -> Result<bool> {
+	let url = format!("{base_url}/user_account/login");
+	let body = format!(
+		"email_address={}&password={}&return_location_path=/",
+		encode_uri_component(email),
+		encode_uri_component(password)
+	);
+	let req = Request::post(&url)?
+		.header("x-requested-with", "XMLHttpRequest")
+		.body(&body);
+	let res = req.send()?;
+
+	match res.status_code() {
+		200 => {
+			let mut jar = CookieJar::default();
+			if let Some(set_cookie) = res.get_header("Set-Cookie") {
+				jar.update(&set_cookie);
+			}
+			if jar.is_empty() {
+				return Err(AidokuError::message("セッションを取得できませんでした"));
+			}
+			jar.save();
+			Ok(true)
+		}
+		400 | 401 | 403 | 422 => Err(AidokuError::message(
+			"メールアドレスまたはパスワードが正しくありません",
+		)),
+		429 => Err(AidokuError::message(
+			"ログインの試行回数が多すぎます。しばらくしてからお試しください",
+		)),
+		status => Err(AidokuError::message(format!(
+			"ログインに失敗しました（{status}）"
+		))),
+	}
+}
+
+/// Log in again with the stored credentials, after the session expired.
+///
+/// The stored cookie is only replaced once the new login succeeded.
+pub fn relogin(base_url: &str) -> Result<bool> {
+	let (Some(email), Some(password)) = (
+		defaults_get::<String>(EMAIL_KEY),
+		defaults_get::<String>(PASSWORD_KEY),
+	) else {
+		return Ok(false);
+	};
+	login(base_url, &email, &password)
+}
+
+pub fn logout() {
+	defaults_set(COOKIE_KEY, DefaultValue::Null);
+}
+
+/// Whether the user entered login credentials
+pub fn has_credentials() -> bool {
+	defaults_get::<String>(EMAIL_KEY).is_some()
+}
+
+/// Whether there is a session to send with requests
+pub fn is_logged_in() -> bool {
+	!CookieJar::load().is_empty()
+}
+
+/// Whether the response redirects to the login page, meaning the session expired
+pub fn is_login_redirect(response: &Response) -> bool {
+	(300..400).contains(&response.status_code())
+		&& response
+			.get_header("Location")
+			.is_some_and(|location| location.contains("/user_account/login"))
+}
+
+/// Keep the cookies the site renews in a response, so the session doesn't expire early
+pub fn store_cookies(response: &Response) {
+	let mut jar = CookieJar::load();
+	if jar.renew(response.get_header("Set-Cookie").as_deref()) {
+		jar.save();
+	}
+}
+
+pub trait AuthedRequest {
+	/// Add the session cookies without sending
+	fn authed(self) -> Self;
+
+	/// Send with the session cookies and store the ones the response renews
+	fn send_authed(self) -> Result<Response>;
+
+	fn html_authed(self) -> Result<Document>;
+
+	fn json_authed<T: DeserializeOwned>(self) -> Result<T>;
+}
+
+impl AuthedRequest for Request {
+	fn authed(self) -> Self {
+		let jar = CookieJar::load();
+		if jar.is_empty() {
+			self
+		} else {
+			self.header("Cookie", &jar.to_header())
+		}
+	}
+
+	fn send_authed(self) -> Result<Response> {
+		let response = self.authed().send()?;
+		store_cookies(&response);
+		Ok(response)
+	}
+
+	fn html_authed(self) -> Result<Document> {
+		String::from_utf8(self.send_authed()?.get_data()?)
+			.ok()
+			.and_then(|body| Html::parse(body).ok())
+			.ok_or_else(|| AidokuError::message("ページを読み込めませんでした"))
+	}
+
+	fn json_authed<T: DeserializeOwned>(self) -> Result<T> {
+		self.send_authed()?.get_json::<T>()
+	}
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,69 @@
+use super::*;
+use aidoku_test::aidoku_test;
+
+#[aidoku_test]
+fn split_keeps_expires_dates() {
+	assert_eq!(
+		split_set_cookie(
+			"glsc=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/; HttpOnly, \
+			 _session=xyz; expires=Thu, 01-Jan-1970 00:00:00 GMT; Max-Age=0"
+		),
+		[
+			"glsc=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/; HttpOnly",
+			"_session=xyz; expires=Thu, 01-Jan-1970 00:00:00 GMT; Max-Age=0",
+		]
+	);
+	assert_eq!(split_set_cookie("glsc=abc"), ["glsc=abc"]);
+}
+
+#[aidoku_test]
+fn update_strips_attributes() {
+	let mut jar = CookieJar::default();
+	jar.update(
+		"glsc=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/; Secure; HttpOnly, \
+		 token=a=b; Domain=.example.com; SameSite=Lax",
+	);
+
+	assert_eq!(jar.to_header(), "glsc=abc; token=a=b");
+}
+
+#[aidoku_test]
+fn update_replaces_and_removes_cookies() {
+	let mut jar = CookieJar::from_header("glsc=old; _session=1; theme=dark");
+	jar.update(
+		"glsc=new; Path=/, _session=; Path=/, \
+		 theme=dark; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=0",
+	);
+
+	assert_eq!(jar.to_header(), "glsc=new");
+
+	jar.update("other=1; max-age=-1");
+	assert_eq!(jar.to_header(), "glsc=new");
+}
+
+#[aidoku_test]
+fn header_round_trip() {
+	let jar = CookieJar::from_header(" glsc=abc ;other=1; broken; =empty");
+
+	assert_eq!(jar.to_header(), "glsc=abc; other=1");
+	assert_eq!(CookieJar::from_header(&jar.to_header()), jar);
+	assert!(CookieJar::from_header("").is_empty());
+}
+
+#[aidoku_test]
+fn renew_merges_into_the_session() {
+	let mut jar = CookieJar::from_header("glsc=abc; other=1");
+	assert!(jar.renew(Some("glsc=def; Path=/; HttpOnly")));
+	assert_eq!(jar.to_header(), "glsc=def; other=1");
+
+	// the same cookies again change nothing, so nothing needs saving
+	assert!(!jar.renew(Some("glsc=def; Path=/")));
+	assert!(!jar.renew(None));
+}
+
+#[aidoku_test]
+fn renew_needs_a_session() {
+	let mut jar = CookieJar::default();
+	assert!(!jar.renew(Some("tracking=1; Path=/")));
+	assert!(jar.is_empty());
+}
@@ -0,0 +1,53 @@
+use aidoku::{
+	alloc::{String, Vec},
+	Manga, MangaPageResult,
+};
+
+/// Series per page when paging through the catalogue locally
+const CATALOGUE_PAGE_SIZE: usize = 50;
+
+/// Listings to search in: the chosen facets, or the whole catalogue without any
+pub fn search_listings(facets: Vec<String>, catalogue_listing: &str) -> Vec<String> {
+	if facets.is_empty() {
+		Vec::from([String::from(catalogue_listing)])
+	} else {
+		facets
+	}
+}
+
+/// Series found in every listing, several facets narrow each other down
+pub fn intersect(listings: impl IntoIterator<Item = Vec<Manga>>) -> Vec<Manga> {
+	let mut listings = listings.into_iter();
+	let Some(first) = listings.next() else {
+		return Vec::new();
+	};
+	listings.fold(first, |entries, found| {
+		entries
+			.into_iter()
+			.filter(|manga| found.iter().any(|other| other.key == manga.key))
+			.collect()
+	})
+}
+
+/// Series whose title contains the query, ignoring case
+pub fn filter_by_title(entries: &mut Vec<Manga>, query: &str) {
+	let query = query.trim().to_lowercase();
+	entries.retain(|manga| manga.title.to_lowercase().contains(&query));
+}
+
+/// The catalogue and facet pages list every series at once, so they're paged here
+pub fn paginate(entries: Vec<Manga>, page: i32) -> MangaPageResult {
+	let start = (page.max(1) as usize - 1) * CATALOGUE_PAGE_SIZE;
+	let has_next_page = entries.len() > start + CATALOGUE_PAGE_SIZE;
+	MangaPageResult {
+		entries: entries
+			.into_iter()
+			.skip(start)
+			.take(CATALOGUE_PAGE_SIZE)
+			.collect(),
+		has_next_page,
+	}
+}
+
+#[cfg(test)]
+mod test;
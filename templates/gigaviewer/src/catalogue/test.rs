@@ -0,0 +1,69 @@
+use super::*;
+use aidoku::{alloc::vec, prelude::*};
+use aidoku_test::aidoku_test;
+
+fn manga(key: &str, title: &str) -> Manga {
+	Manga {
+		key: key.into(),
+		title: title.into(),
+		..Default::default()
+	}
+}
+
+fn keys(entries: &[Manga]) -> Vec<&str> {
+	entries.iter().map(|manga| manga.key.as_str()).collect()
+}
+
+#[aidoku_test]
+fn catalogue_without_facets() {
+	assert_eq!(search_listings(Vec::new(), "series"), ["series"]);
+	assert_eq!(
+		search_listings(vec!["series/oneshot".into()], "series"),
+		["series/oneshot"]
+	);
+}
+
+#[aidoku_test]
+fn facets_narrow_each_other_down() {
+	let ongoing = vec![manga("/episode/1", "A"), manga("/episode/2", "B")];
+	let oneshots = vec![manga("/episode/2", "B"), manga("/episode/3", "C")];
+
+	assert_eq!(
+		keys(&intersect([ongoing.clone()])),
+		["/episode/1", "/episode/2"]
+	);
+	assert_eq!(keys(&intersect([ongoing, oneshots])), ["/episode/2"]);
+	assert!(intersect(Vec::<Vec<Manga>>::new()).is_empty());
+}
+
+#[aidoku_test]
+fn titles_filtered_ignoring_case() {
+	let mut entries = vec![
+		manga("/episode/1", "SPY×FAMILY"),
+		manga("/episode/2", "ダンダダン"),
+	];
+	filter_by_title(&mut entries, " spy ");
+	assert_eq!(keys(&entries), ["/episode/1"]);
+}
+
+#[aidoku_test]
+fn pages_of_the_catalogue() {
+	let entries = || -> Vec<Manga> {
+		(1..=120)
+			.map(|i| manga(&format!("/episode/{i}"), "title"))
+			.collect()
+	};
+
+	let first = paginate(entries(), 1);
+	assert_eq!(first.entries.len(), 50);
+	assert_eq!(first.entries[0].key, "/episode/1");
+	assert!(first.has_next_page);
+
+	let last = paginate(entries(), 3);
+	assert_eq!(last.entries.len(), 20);
+	assert_eq!(last.entries[0].key, "/episode/101");
+	assert!(!last.has_next_page);
+
+	assert!(paginate(entries(), 4).entries.is_empty());
+	assert_eq!(paginate(entries(), 0).entries[0].key, "/episode/1");
+}
@@ -0,0 +1,50 @@
+use aidoku::alloc::Vec;
+
+/// Tiles per row and column when the episode JSON doesn't say
+pub const DEFAULT_DIVIDE_NUM: i32 = 4;
+/// Tile sizes are rounded down to a multiple of this
+pub const DEFAULT_MULTIPLE: i32 = 8;
+
+/// Area of a page image, in pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+	pub x: i32,
+	pub y: i32,
+	pub width: i32,
+	pub height: i32,
+}
+
+/// Copies (source, destination) that undo the tile shuffle of a scrambled page.
+///
+/// The page is split into `divide_num` × `divide_num` tiles, which the CDN transposes.
+/// Pixels to the right and below the grid aren't scrambled and stay where they are.
+/// Returns nothing when the image is too small for the grid or the parameters are invalid.
+pub fn tile_moves(width: i32, height: i32, divide_num: i32, multiple: i32) -> Vec<(Cell, Cell)> {
+	if divide_num < 2 || multiple < 1 || width <= 0 || height <= 0 {
+		return Vec::new();
+	}
+
+	let cell_width = width / (divide_num * multiple) * multiple;
+	let cell_height = height / (divide_num * multiple) * multiple;
+	if cell_width == 0 || cell_height == 0 {
+		return Vec::new();
+	}
+
+	let cell = |index: i32| Cell {
+		x: index % divide_num * cell_width,
+		y: index / divide_num * cell_height,
+		width: cell_width,
+		height: cell_height,
+	};
+
+	(0..divide_num * divide_num)
+		.map(|index| {
+			let row = index / divide_num;
+			let column = index % divide_num;
+			(cell(index), cell(column * divide_num + row))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,127 @@
+use super::*;
+use aidoku::alloc::{vec, Vec};
+use aidoku_test::aidoku_test;
+
+/// A fake image where every pixel holds its own index in the unscrambled page
+struct Image {
+	width: i32,
+	height: i32,
+	pixels: Vec<i32>,
+}
+
+impl Image {
+	fn unscrambled(width: i32, height: i32) -> Self {
+		Self {
+			width,
+			height,
+			pixels: (0..width * height).collect(),
+		}
+	}
+
+	/// What the app does: draw the whole image, then copy every tile over it
+	fn apply(&self, moves: &[(Cell, Cell)]) -> Self {
+		let mut pixels = self.pixels.clone();
+		for (src, dst) in moves {
+			for dy in 0..src.height {
+				for dx in 0..src.width {
+					let from = (src.y + dy) * self.width + src.x + dx;
+					let to = (dst.y + dy) * self.width + dst.x + dx;
+					pixels[to as usize] = self.pixels[from as usize];
+				}
+			}
+		}
+		Self {
+			width: self.width,
+			height: self.height,
+			pixels,
+		}
+	}
+}
+
+#[aidoku_test]
+fn known_moves_for_4x4_grid() {
+	let moves = tile_moves(64, 64, DEFAULT_DIVIDE_NUM, DEFAULT_MULTIPLE);
+	assert_eq!(moves.len(), 16);
+
+	let cell = |x, y| Cell {
+		x,
+		y,
+		width: 16,
+		height: 16,
+	};
+	// diagonal tiles stay, the others are transposed
+	assert_eq!(moves[0], (cell(0, 0), cell(0, 0)));
+	assert_eq!(moves[1], (cell(16, 0), cell(0, 16)));
+	assert_eq!(moves[4], (cell(0, 16), cell(16, 0)));
+	assert_eq!(moves[7], (cell(48, 16), cell(16, 48)));
+	assert_eq!(moves[15], (cell(48, 48), cell(48, 48)));
+}
+
+#[aidoku_test]
+fn moves_are_their_own_inverse() {
+	// the shuffle is a transpose, so the same moves scramble and descramble
+	let page = Image::unscrambled(64, 48);
+	let moves = tile_moves(page.width, page.height, 4, 8);
+	let scrambled = page.apply(&moves);
+	assert_ne!(scrambled.pixels, page.pixels);
+	assert_eq!(scrambled.apply(&moves).pixels, page.pixels);
+}
+
+#[aidoku_test]
+fn edges_outside_the_grid_are_untouched() {
+	// 100x70 gives 24x16 tiles, leaving 4 columns and 6 rows unscrambled
+	let page = Image::unscrambled(100, 70);
+	let moves = tile_moves(page.width, page.height, 4, 8);
+	assert!(moves
+		.iter()
+		.all(|(src, dst)| src.x + src.width <= 96 && dst.y + dst.height <= 64));
+
+	let descrambled = page.apply(&moves);
+	for y in 0..page.height {
+		for x in 0..page.width {
+			if x >= 96 || y >= 64 {
+				let i = (y * page.width + x) as usize;
+				assert_eq!(descrambled.pixels[i], page.pixels[i]);
+			}
+		}
+	}
+}
+
+#[aidoku_test]
+fn fixture_3x3_grid() {
+	// 6x6 page with 2x2 tiles, tiles labeled 0-8 in reading order
+	#[rustfmt::skip]
+	let scrambled = vec![
+		0, 0, 3, 3, 6, 6,
+		0, 0, 3, 3, 6, 6,
+		1, 1, 4, 4, 7, 7,
+		1, 1, 4, 4, 7, 7,
+		2, 2, 5, 5, 8, 8,
+		2, 2, 5, 5, 8, 8,
+	];
+	#[rustfmt::skip]
+	let unscrambled = vec![
+		0, 0, 1, 1, 2, 2,
+		0, 0, 1, 1, 2, 2,
+		3, 3, 4, 4, 5, 5,
+		3, 3, 4, 4, 5, 5,
+		6, 6, 7, 7, 8, 8,
+		6, 6, 7, 7, 8, 8,
+	];
+
+	let image = Image {
+		width: 6,
+		height: 6,
+		pixels: scrambled,
+	};
+	let moves = tile_moves(6, 6, 3, 2);
+	assert_eq!(image.apply(&moves).pixels, unscrambled);
+}
+
+#[aidoku_test]
+fn invalid_parameters_skip_descrambling() {
+	assert!(tile_moves(20, 20, 4, 8).is_empty());
+	assert!(tile_moves(0, 100, 4, 8).is_empty());
+	assert!(tile_moves(100, 100, 1, 8).is_empty());
+	assert!(tile_moves(100, 100, 4, 0).is_empty());
+}
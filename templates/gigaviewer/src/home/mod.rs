@@ -0,0 +1,224 @@
+use aidoku::{
+	alloc::{vec, String, Vec},
+	imports::{html::Document, net::Request},
+	prelude::*,
+	HomeComponent, HomeComponentValue, HomeLayout, Link, LinkValue, Listing, ListingKind, Manga,
+	Result,
+};
+
+use crate::{
+	weekday::{self, Weekday, WEEKDAY_PLACEHOLDER},
+	AuthedRequest, HomeSection, HomeSectionKind, ListSelectors, Params,
+};
+
+/// Sections found on most GigaViewer top pages
+pub fn default_sections() -> Vec<HomeSection> {
+	vec![
+		HomeSection {
+			title: None,
+			subtitle: None,
+			subtitle_selector: None,
+			kind: HomeSectionKind::Banner {
+				width: 341,
+				height: 128,
+			},
+			selectors: ListSelectors {
+				item: ".swiper-slide:not(.swiper-slide-duplicate) a".into(),
+				title: "img".into(),
+				cover: "img".into(),
+				cover_attr: "src".into(),
+				authors: None,
+				description: None,
+			},
+			listing: None,
+		},
+		HomeSection {
+			title: Some("今日のランキング".into()),
+			subtitle: None,
+			subtitle_selector: Some(".date-wrapper".into()),
+			kind: HomeSectionKind::Ranking,
+			selectors: ListSelectors {
+				item: ".daily:first-child .daily-series-item".into(),
+				title: "h2".into(),
+				cover: "img".into(),
+				cover_attr: "src".into(),
+				authors: Some(".daily-series-author".into()),
+				description: Some("p".into()),
+			},
+			listing: None,
+		},
+		HomeSection {
+			title: Some("今日更新の連載".into()),
+			subtitle: None,
+			subtitle_selector: None,
+			kind: HomeSectionKind::Scroller,
+			selectors: weekday::default_selectors(),
+			listing: Some(("weekday/today".into(), "今日更新の連載".into())),
+		},
+		HomeSection {
+			title: Some("総合ランキング".into()),
+			subtitle: Some("今話題の作品をチェック！".into()),
+			subtitle_selector: None,
+			kind: HomeSectionKind::Scroller,
+			selectors: link_selectors(".total-ranking-list-sp a"),
+			listing: None,
+		},
+		HomeSection {
+			title: Some("無料キャンペーン・復刻連載".into()),
+			subtitle: Some("話題作や名作が今だけ無料の大公開！".into()),
+			subtitle_selector: None,
+			kind: HomeSectionKind::Scroller,
+			selectors: link_selectors(".free-campaign a"),
+			listing: None,
+		},
+		HomeSection {
+			title: Some("新作読切".into()),
+			subtitle: None,
+			subtitle_selector: None,
+			kind: HomeSectionKind::Scroller,
+			selectors: link_selectors(".oneshot-list a"),
+			listing: None,
+		},
+	]
+}
+
+/// Selectors for the card links used by most top page sections
+pub fn link_selectors(item: &'static str) -> ListSelectors {
+	ListSelectors {
+		item: item.into(),
+		title: "h3".into(),
+		cover: "img".into(),
+		cover_attr: "src".into(),
+		authors: None,
+		description: None,
+	}
+}
+
+pub fn get_home(params: &Params) -> Result<HomeLayout> {
+	let html = Request::get(params.base_url.as_ref())?.html_authed()?;
+
+	let today = Weekday::today();
+	let mut weekday_html = None;
+
+	let mut components = Vec::new();
+	for section in params.home_sections.iter() {
+		if !section.selectors.item.contains(WEEKDAY_PLACEHOLDER) {
+			components.extend(parse_section(&html, params, section));
+			continue;
+		}
+
+		// ex: https://shonenjumpplus.com/series, only fetched when a section needs it
+		let page = weekday_html.get_or_insert_with(|| {
+			let url = format!("{}/{}", params.base_url, params.weekday_path);
+			Request::get(url)
+				.ok()
+				.and_then(|request| request.html_authed().ok())
+		});
+		if let Some(page) = page {
+			let section = HomeSection {
+				selectors: today.fill_selectors(&section.selectors),
+				..section.clone()
+			};
+			components.extend(parse_section(page, params, &section));
+		}
+	}
+
+	Ok(HomeLayout { components })
+}
+
+fn parse_section(html: &Document, params: &Params, section: &HomeSection) -> Option<HomeComponent> {
+	let listing = section.listing.as_ref().map(|(id, name)| Listing {
+		id: id.clone().into(),
+		name: name.clone().into(),
+		kind: ListingKind::List,
+	});
+
+	let value = match section.kind {
+		HomeSectionKind::Banner { width, height } => {
+			let links = parse_banners(html, &params.base_url, &section.selectors);
+			if links.is_empty() {
+				return None;
+			}
+			HomeComponentValue::ImageScroller {
+				links,
+				auto_scroll_interval: Some(4.0),
+				width: Some(width),
+				height: Some(height),
+			}
+		}
+		HomeSectionKind::Ranking => {
+			let entries = parse_links(html, &params.base_url, &section.selectors);
+			if entries.is_empty() {
+				return None;
+			}
+			HomeComponentValue::MangaList {
+				ranking: true,
+				page_size: Some(3),
+				entries,
+				listing,
+			}
+		}
+		HomeSectionKind::Scroller => {
+			let entries = parse_links(html, &params.base_url, &section.selectors);
+			if entries.is_empty() {
+				return None;
+			}
+			HomeComponentValue::Scroller { entries, listing }
+		}
+	};
+
+	let subtitle = section
+		.subtitle_selector
+		.as_ref()
+		.and_then(|selector| html.select_first(selector.as_ref())?.text())
+		.or_else(|| section.subtitle.clone().map(String::from));
+
+	Some(HomeComponent {
+		title: section.title.clone().map(String::from),
+		subtitle,
+		value,
+	})
+}
+
+fn parse_links(html: &Document, base_url: &str, selectors: &ListSelectors) -> Vec<Link> {
+	selectors
+		.parse(html, base_url)
+		.into_iter()
+		.map(|manga| manga.into())
+		.collect()
+}
+
+/// Banners open series and episodes in the app and anything else (campaigns) in the browser
+fn parse_banners(html: &Document, base_url: &str, selectors: &ListSelectors) -> Vec<Link> {
+	let manga_prefixes = [format!("{base_url}/episode"), format!("{base_url}/series")];
+	html.select(selectors.item.as_ref())
+		.map(|elements| {
+			elements
+				.filter_map(|e| {
+					let image = e
+						.select_first(selectors.cover.as_ref())
+						.and_then(|x| x.attr(selectors.cover_attr.as_ref()))?;
+					let url = e.attr("href")?;
+					let value = if manga_prefixes.iter().any(|prefix| url.starts_with(prefix)) {
+						let key = url.strip_prefix(base_url).map(String::from)?;
+						LinkValue::Manga(Manga {
+							key,
+							..Default::default()
+						})
+					} else {
+						LinkValue::Url(url)
+					};
+					Some(Link {
+						title: String::default(),
+						image_url: Some(image),
+						value: Some(value),
+						..Default::default()
+					})
+				})
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,99 @@
+use super::*;
+use aidoku::imports::html::Html;
+use aidoku_test::aidoku_test;
+
+const BASE_URL: &str = "https://shonenjumpplus.com";
+
+fn parse(html: &str) -> Document {
+	Html::parse(String::from(html)).expect("valid html")
+}
+
+fn params() -> Params {
+	Params {
+		base_url: BASE_URL.into(),
+		..Default::default()
+	}
+}
+
+fn section(title: &str) -> HomeSection {
+	default_sections()
+		.into_iter()
+		.find(|section| section.title.as_deref() == Some(title))
+		.expect("default section")
+}
+
+#[aidoku_test]
+fn banners_open_series_in_the_app() {
+	let html = parse(include_str!("fixtures/top.html"));
+	let banner = &default_sections()[0];
+
+	let links = parse_banners(&html, BASE_URL, &banner.selectors);
+	assert_eq!(links.len(), 3);
+	assert_eq!(
+		links[0].image_url.as_deref(),
+		Some("https://cdn-img.shonenjumpplus.com/banner/spy.jpg")
+	);
+	match &links[0].value {
+		Some(LinkValue::Manga(manga)) => {
+			assert_eq!(manga.key, "/episode/10834108156648240735")
+		}
+		_ => panic!("episode banner should open the series"),
+	}
+	match &links[1].value {
+		Some(LinkValue::Manga(manga)) => assert_eq!(manga.key, "/series/10834108156766380123"),
+		_ => panic!("series banner should open the series"),
+	}
+	match &links[2].value {
+		Some(LinkValue::Url(url)) => assert_eq!(url, "https://shonenjumpplus.com/campaign/2024"),
+		_ => panic!("campaign banner should open in the browser"),
+	}
+}
+
+#[aidoku_test]
+fn ranking_takes_its_subtitle_from_the_page() {
+	let html = parse(include_str!("fixtures/top.html"));
+
+	let component = parse_section(&html, &params(), &section("今日のランキング")).expect("ranking");
+	assert_eq!(component.title.as_deref(), Some("今日のランキング"));
+	assert_eq!(component.subtitle.as_deref(), Some("1月1日(月)"));
+	match component.value {
+		HomeComponentValue::MangaList {
+			ranking, entries, ..
+		} => {
+			assert!(ranking);
+			assert_eq!(entries.len(), 2);
+			assert_eq!(entries[0].title, "SPY×FAMILY");
+		}
+		_ => panic!("ranking should be a manga list"),
+	}
+}
+
+#[aidoku_test]
+fn sections_without_entries_are_left_out() {
+	let html = parse(include_str!("fixtures/top.html"));
+
+	assert!(parse_section(&html, &params(), &section("新作読切")).is_none());
+}
+
+#[aidoku_test]
+fn today_section_reads_the_weekday_page() {
+	let html = parse(include_str!("fixtures/series.html"));
+	let today = section("今日更新の連載");
+	let monday = HomeSection {
+		selectors: Weekday::Monday.fill_selectors(&today.selectors),
+		..today
+	};
+
+	let component = parse_section(&html, &params(), &monday).expect("today's series");
+	match component.value {
+		HomeComponentValue::Scroller { entries, listing } => {
+			assert_eq!(entries.len(), 1);
+			assert_eq!(entries[0].title, "SPY×FAMILY");
+			assert_eq!(
+				listing.map(|listing| listing.id).as_deref(),
+				Some("weekday/today")
+			);
+		}
+		_ => panic!("today's series should be a scroller"),
+	}
+}
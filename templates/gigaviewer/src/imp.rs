@@ -1,10 +1,21 @@
-use super::{auth, models::*, parser, AuthedRequest, Params};
+use super::{
+	auth, catalogue,
+	descramble::{tile_moves, Cell, DEFAULT_DIVIDE_NUM, DEFAULT_MULTIPLE},
+	home,
+	links::{parse_link, GigaLink},
+	models::*,
+	parser,
+	product::{get_readable_product_update, is_readable_product_key},
+	weekday::Weekday,
+	AuthedRequest, Params,
+};
 use aidoku::{
 	alloc::{string::ToString, String, Vec},
 	helpers::uri::QueryParameters,
 	imports::{
 		canvas::{Canvas, ImageRef, Rect},
 		error::AidokuError,
+		html::Html,
 		net::Request,
 		std::send_partial_result,
 	},
@@ -13,6 +24,14 @@ use aidoku::{
 	MangaPageResult, Page, PageContent, PageContext, Result, Viewer,
 };
 
+/// Listing with the series whose serialization ended
+pub const COMPLETED_LISTING: &str = "completed";
+
+/// Listing with the magazine issues on sale
+pub const MAGAZINE_LISTING: &str = "magazine";
+/// Listing with the volumes (tankōbon) on sale
+pub const VOLUME_LISTING: &str = "volume";
+
 pub trait Impl {
 	fn new() -> Self;
 
@@ -20,44 +39,88 @@ pub trait Impl {
 
 	fn get_manga_list(
 		&self,
-		_params: &Params,
-		_listing: Listing,
+		params: &Params,
+		listing: Listing,
 		_page: i32,
 	) -> Result<MangaPageResult> {
-		Err(AidokuError::message("Invalid listing"))
+		let entries = self.get_listing_entries(params, &listing.id)?;
+		if entries.is_empty() {
+			return Err(AidokuError::message("Invalid listing"));
+		}
+
+		Ok(MangaPageResult {
+			entries,
+			has_next_page: false,
+		})
+	}
+
+	/// Series of a listing, also used for the search facets.
+	/// Sites can override this for listing pages with their own layout.
+	fn get_listing_entries(&self, params: &Params, id: &str) -> Result<Vec<Manga>> {
+		listing_entries(params, id)
 	}
 
 	fn get_search_manga_list(
 		&self,
 		params: &Params,
 		query: Option<String>,
-		_page: i32,
-		_filters: Vec<FilterValue>,
+		page: i32,
+		filters: Vec<FilterValue>,
 	) -> Result<MangaPageResult> {
-		let Some(query) = query else {
-			return Ok(MangaPageResult::default());
-		};
+		let query = query.filter(|q| !q.trim().is_empty());
+
+		// label facets are site listings, ex: `series/oneshot`
+		let facets: Vec<String> = filters
+			.into_iter()
+			.filter_map(|filter| match filter {
+				FilterValue::Select { value, .. } if !value.is_empty() => Some(value),
+				_ => None,
+			})
+			.collect();
+
+		if let Some(query) = &query {
+			if facets.is_empty() {
+				let mut qs = QueryParameters::new();
+				qs.push("q", Some(query));
+				if page > 1 {
+					qs.push("page", Some(&page.to_string()));
+				}
+				let url = format!("{}/search?{}", params.base_url, qs);
+				let html = Request::get(url)?.html_authed()?;
 
-		let mut qs = QueryParameters::new();
-		qs.push("q", Some(&query));
-		let url = format!("{}/search?{}", params.base_url, qs);
-		let html = Request::get(url)?.authed().html()?;
-
-		let entries = parser::parse_response(
-			&html,
-			&params.base_url,
-			"ul.search-series-list li, ul.series-list li",
-			"div.title-box p.series-title",
-			"div.thmb-container a img",
-			"src",
-			None,
-			None,
-		);
+				let entries = parser::parse_response(
+					&html,
+					&params.base_url,
+					"ul.search-series-list li, ul.series-list li",
+					"div.title-box p.series-title",
+					"div.thmb-container a img",
+					"src",
+					None,
+					None,
+				);
+				let has_next_page = !entries.is_empty()
+					&& html
+						.select_first(params.search_next_page_selector.as_ref())
+						.is_some();
+
+				return Ok(MangaPageResult {
+					entries,
+					has_next_page,
+				});
+			}
+		}
 
-		Ok(MangaPageResult {
-			entries,
-			has_next_page: false,
-		})
+		// an empty facet page is an empty result, not an error
+		let found = catalogue::search_listings(facets, &params.catalogue_listing)
+			.iter()
+			.map(|id| self.get_listing_entries(params, id))
+			.collect::<Result<Vec<_>>>()?;
+		let mut entries = catalogue::intersect(found);
+		if let Some(query) = query {
+			catalogue::filter_by_title(&mut entries, &query);
+		}
+
+		Ok(catalogue::paginate(entries, page))
 	}
 
 	fn get_manga_update(
@@ -67,10 +130,14 @@ pub trait Impl {
 		needs_details: bool,
 		needs_chapters: bool,
 	) -> Result<Manga> {
+		if is_readable_product_key(&manga.key) {
+			return get_readable_product_update(params, manga, needs_details, needs_chapters);
+		}
+
 		let mut new_manga = manga.clone();
 
 		let url = format!("{}{}", params.base_url, manga.key);
-		let html = Request::get(&url)?.authed().html()?;
+		let html = Request::get(&url)?.html_authed()?;
 
 		if needs_details {
 			let element = html
@@ -135,29 +202,24 @@ pub trait Impl {
 		Ok(new_manga)
 	}
 
-	fn get_page_list(
-		&self,
-		_params: &Params,
-		_manga: Manga,
-		chapter: Chapter,
-	) -> Result<Vec<Page>> {
+	fn get_page_list(&self, params: &Params, _manga: Manga, chapter: Chapter) -> Result<Vec<Page>> {
 		let Some(url) = chapter.url else {
 			return Err(AidokuError::message("URLがありません"));
 		};
-		let html = Request::get(url)?.authed().html()?;
-
-		let episode = html
-			.select_first("script#episode-json")
-			.and_then(|e| e.attr("data-value"))
-			.ok_or(AidokuError::message("このチャプターは非公開です"))
-			.and_then(|v| {
-				serde_json::from_str::<GigaEpisode>(v.as_ref())
-					.map_err(|_| AidokuError::JsonParseError)
-			})?;
-
-		Ok(episode
-			.readable_product
-			.page_structure
+		let mut episode = fetch_episode(&url)?;
+
+		// a chapter listed as readable without pages means the session expired
+		let expired = match &episode {
+			Some(episode) => !chapter.locked && !episode.has_main_pages() && auth::is_logged_in(),
+			None => true,
+		};
+		if expired && auth::has_credentials() && auth::relogin(&params.base_url)? {
+			episode = fetch_episode(&url)?;
+		}
+		let episode = episode.ok_or(AidokuError::message("ログインの有効期限が切れました"))?;
+
+		let structure = &episode.readable_product.page_structure;
+		Ok(structure
 			.pages
 			.iter()
 			.filter_map(|page| {
@@ -165,9 +227,17 @@ pub trait Impl {
 					return None;
 				}
 				let src = page.src.as_ref()?;
+				let scrambled = page.is_scrambled.unwrap_or(structure.is_scrambled());
 				let mut context = PageContext::new();
 				context.insert(String::from("width"), page.width.unwrap_or(0).to_string());
 				context.insert(String::from("height"), page.height.unwrap_or(0).to_string());
+				context.insert(String::from("scrambled"), scrambled.to_string());
+				if let Some(divide_num) = structure.divide_num {
+					context.insert(String::from("divide_num"), divide_num.to_string());
+				}
+				if let Some(multiple) = structure.multiple {
+					context.insert(String::from("multiple"), multiple.to_string());
+				}
 				Some(Page {
 					content: PageContent::url_context(src, context),
 					..Default::default()
@@ -182,9 +252,8 @@ pub trait Impl {
 		response: ImageResponse,
 		context: Option<PageContext>,
 	) -> Result<ImageRef> {
-		let Some(context) = context else {
-			return Err(AidokuError::message("Missing page context"));
-		};
+		// without context (e.g. a cached page), descramble with the defaults and the image's size
+		let context = context.unwrap_or_default();
 
 		// if the image is not from the specified CDN, return the original image without trying to descramble
 		if response
@@ -194,21 +263,25 @@ pub trait Impl {
 		{
 			return Ok(response.image);
 		}
+		if context.get("scrambled").is_some_and(|s| s == "false") {
+			return Ok(response.image);
+		}
 
-		let width = context
-			.get("width")
-			.and_then(|w| w.parse::<usize>().ok())
-			.unwrap_or(0);
-		let height = context
-			.get("height")
-			.and_then(|h| h.parse::<usize>().ok())
-			.unwrap_or(0);
-
-		const DIVIDE_NUM: i32 = 4;
-		const MULTIPLE: i32 = 8;
+		let get = |key: &str| {
+			context
+				.get(key)
+				.and_then(|value| value.parse::<i32>().ok())
+				.filter(|&value| value > 0)
+		};
+		let width = get("width").unwrap_or(response.image.width() as i32);
+		let height = get("height").unwrap_or(response.image.height() as i32);
+		let divide_num = get("divide_num").unwrap_or(DEFAULT_DIVIDE_NUM);
+		let multiple = get("multiple").unwrap_or(DEFAULT_MULTIPLE);
 
-		let c_width = ((width as f32) / (DIVIDE_NUM * MULTIPLE) as f32) as i32 * MULTIPLE;
-		let c_height = ((height as f32) / (DIVIDE_NUM * MULTIPLE) as f32) as i32 * MULTIPLE;
+		let moves = tile_moves(width, height, divide_num, multiple);
+		if moves.is_empty() {
+			return Ok(response.image);
+		}
 
 		let mut canvas = Canvas::new(width as f32, height as f32);
 
@@ -216,25 +289,23 @@ pub trait Impl {
 		let img_rect = Rect::new(0.0, 0.0, width as f32, height as f32);
 		canvas.draw_image(&response.image, img_rect);
 
-		for e in 0..DIVIDE_NUM * DIVIDE_NUM {
-			let x = e % DIVIDE_NUM * c_width;
-			let y = (e as f32 / DIVIDE_NUM as f32) as i32 * c_height;
-			let cell_src = Rect::new(x as f32, y as f32, c_width as f32, c_height as f32);
-
-			let row = (e as f32 / DIVIDE_NUM as f32) as i32;
-			let dst_e = e % DIVIDE_NUM * DIVIDE_NUM + row;
-			let dst_x = dst_e % DIVIDE_NUM * c_width;
-			let dst_y = (dst_e as f32 / DIVIDE_NUM as f32) as i32 * c_height;
-			let cell_dst = Rect::new(dst_x as f32, dst_y as f32, c_width as f32, c_height as f32);
-
-			canvas.copy_image(&response.image, cell_src, cell_dst);
+		let rect = |cell: Cell| {
+			Rect::new(
+				cell.x as f32,
+				cell.y as f32,
+				cell.width as f32,
+				cell.height as f32,
+			)
+		};
+		for (src, dst) in moves {
+			canvas.copy_image(&response.image, rect(src), rect(dst));
 		}
 
 		Ok(canvas.get_image())
 	}
 
-	fn get_home(&self, _params: &Params) -> Result<HomeLayout> {
-		Err(AidokuError::Unimplemented)
+	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
+		home::get_home(params)
 	}
 
 	fn handle_basic_login(
@@ -250,8 +321,8 @@ pub trait Impl {
 	fn handle_notification(&self, _params: &Params, notification: String) {
 		// handle log out
 		if notification == "login" {
-			let logged_in = auth::is_logged_in();
-			if !logged_in {
+			let has_credentials = auth::has_credentials();
+			if !has_credentials {
 				// if the username/password default keys were removed, we should remove the cookie key
 				auth::logout();
 			}
@@ -259,21 +330,101 @@ pub trait Impl {
 	}
 
 	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
-		let Some(path) = url.strip_prefix(params.base_url.as_ref()) else {
-			return Ok(None);
-		};
-
-		const EPISODE_PATH: &str = "episode/";
+		match parse_link(&url, &params.base_url) {
+			Some(GigaLink::Episode(path)) => {
+				// ex: https://shonenjumpplus.com/episode/10834108156648240735
+				// series lists link to the first episode, so that's the manga key,
+				// but the episode can still be opened on its own
+				let manga_key = resolve_series_key(params, path)
+					.ok()
+					.flatten()
+					.unwrap_or_else(|| path.into());
+				Ok(Some(DeepLinkResult::Chapter {
+					manga_key,
+					key: path.into(),
+				}))
+			}
+			Some(GigaLink::Series(path)) => {
+				// ex: https://comic-days.com/series/10834108156766380123
+				// the id in the URL isn't the aggregate id, which is on the series page
+				let key = resolve_series_key(params, path)?
+					.ok_or(AidokuError::message("作品が見つかりません"))?;
+				Ok(Some(DeepLinkResult::Manga { key }))
+			}
+			Some(GigaLink::Magazine(path) | GigaLink::Volume(path)) => {
+				// ex: https://shonenjumpplus.com/magazine/10834108156649174891
+				// open the series when the product belongs to one, otherwise the product itself
+				let key = resolve_series_key(params, path)?.unwrap_or_else(|| path.into());
+				Ok(Some(DeepLinkResult::Manga { key }))
+			}
+			None => Ok(None),
+		}
+	}
+}
 
-		if let Some(key) = path.strip_prefix(EPISODE_PATH) {
-			// ex: https://shonenjumpplus.com/episode/10834108156648240735
-			// the manga key can be any of the chapter keys
-			Ok(Some(DeepLinkResult::Chapter {
-				manga_key: key.into(),
-				key: key.into(),
-			}))
+/// Series of a listing page, the catalogue, a facet, a weekday or the products on sale
+pub fn listing_entries(params: &Params, id: &str) -> Result<Vec<Manga>> {
+	Ok(if let Some(weekday) = Weekday::from_listing_id(id) {
+		// ex: https://comic-days.com/series, one section per weekday
+		let url = format!("{}/{}", params.base_url, params.weekday_path);
+		let html = Request::get(url)?.html_authed()?;
+		weekday
+			.fill_selectors(&params.weekday_selectors)
+			.parse(&html, &params.base_url)
+	} else {
+		// ex: https://shonenjumpplus.com/series/finished
+		let path = match id {
+			COMPLETED_LISTING => params.completed_path.as_ref(),
+			MAGAZINE_LISTING => params.magazine_path.as_ref(),
+			VOLUME_LISTING => params.volume_path.as_ref(),
+			id => id,
+		};
+		let url = format!("{}/{}", params.base_url, path);
+		let html = Request::get(url)?.html_authed()?;
+		if id == MAGAZINE_LISTING || id == VOLUME_LISTING {
+			params.product_selectors.parse(&html, &params.base_url)
 		} else {
-			Ok(None)
+			params.catalogue_selectors.parse(&html, &params.base_url)
+		}
+	})
+}
+
+/// Fetch the episode JSON of a viewer page, `None` when the session expired
+fn fetch_episode(url: &str) -> Result<Option<GigaEpisode>> {
+	let response = Request::get(url)?.send_authed()?;
+	if auth::is_login_redirect(&response) {
+		return Ok(None);
+	}
+	// an undecodable page is an error, not an expired session
+	let html = String::from_utf8(response.get_data()?)
+		.ok()
+		.and_then(|body| Html::parse(body).ok())
+		.ok_or_else(|| AidokuError::message("ページを読み込めませんでした"))?;
+
+	let Some(value) = html
+		.select_first("script#episode-json")
+		.and_then(|e| e.attr("data-value"))
+	else {
+		// redirects are followed, so an expired session can also end up on the login form
+		if html
+			.select_first("form[action*='/user_account/login']")
+			.is_some()
+		{
+			return Ok(None);
 		}
+		return Err(AidokuError::message("このチャプターは非公開です"));
+	};
+
+	serde_json::from_str::<GigaEpisode>(value.as_ref())
+		.map(Some)
+		.map_err(|_| AidokuError::JsonParseError)
+}
+
+/// Key of the series a readable product (episode, magazine issue or volume) belongs to
+fn resolve_series_key(params: &Params, path: &str) -> Result<Option<String>> {
+	let html = Request::get(format!("{}{path}", params.base_url))?.html_authed()?;
+	match parser::parse_aggregate_id(&html) {
+		Some(aggregate_id) => parser::first_episode_key(&params.base_url, &aggregate_id),
+		None => Ok(None),
 	}
 }
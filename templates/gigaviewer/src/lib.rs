@@ -1,19 +1,80 @@
 #![no_std]
 use aidoku::{
 	alloc::{borrow::Cow, String, Vec},
-	imports::canvas::ImageRef,
+	imports::{canvas::ImageRef, html::Document},
 	BasicLoginHandler, Chapter, DeepLinkHandler, DeepLinkResult, FilterValue, Home, HomeLayout,
 	ImageResponse, Listing, ListingProvider, Manga, MangaPageResult, NotificationHandler, Page,
 	PageContext, PageImageProcessor, Result, Source,
 };
 
 mod auth;
+mod catalogue;
+mod descramble;
+mod home;
 mod imp;
+mod links;
 mod models;
 pub mod parser;
+mod product;
+mod weekday;
 
 pub use auth::AuthedRequest;
-pub use imp::Impl;
+pub use home::link_selectors;
+pub use imp::{listing_entries, Impl};
+
+/// Selectors for a list of series, as passed to `parser::parse_response`
+#[derive(Clone)]
+pub struct ListSelectors {
+	pub item: Cow<'static, str>,
+	pub title: Cow<'static, str>,
+	pub cover: Cow<'static, str>,
+	pub cover_attr: Cow<'static, str>,
+	pub authors: Option<Cow<'static, str>>,
+	pub description: Option<Cow<'static, str>>,
+}
+
+impl ListSelectors {
+	pub fn parse(&self, html: &Document, base_url: &str) -> Vec<Manga> {
+		parser::parse_response(
+			html,
+			base_url,
+			self.item.as_ref(),
+			self.title.as_ref(),
+			self.cover.as_ref(),
+			self.cover_attr.as_ref(),
+			self.authors.as_deref(),
+			self.description.as_deref(),
+		)
+	}
+}
+
+/// How a home section is displayed
+#[derive(Clone, Copy)]
+pub enum HomeSectionKind {
+	/// Image banners linking to series, episodes or campaign pages
+	Banner {
+		width: i32,
+		height: i32,
+	},
+	/// Numbered list, three entries per page
+	Ranking,
+	Scroller,
+}
+
+/// Section of the home page, scraped from the site's top page,
+/// or from today's series on the weekday page when `item` contains `{weekday}`
+#[derive(Clone)]
+pub struct HomeSection {
+	pub title: Option<Cow<'static, str>>,
+	pub subtitle: Option<Cow<'static, str>>,
+	/// Subtitle on the page (e.g. the ranking date), used instead of `subtitle` when found
+	pub subtitle_selector: Option<Cow<'static, str>>,
+	pub kind: HomeSectionKind,
+	/// For banners, `item` selects the links and `cover` their images
+	pub selectors: ListSelectors,
+	/// Listing id and name opened from the section
+	pub listing: Option<(Cow<'static, str>, Cow<'static, str>)>,
+}
 
 pub struct Params {
 	pub base_url: Cow<'static, str>,
@@ -21,6 +82,26 @@ pub struct Params {
 	pub is_paginated: bool,
 	pub popular_item_selector: Cow<'static, str>,
 	pub chapter_list_selector: Cow<'static, str>,
+	/// Listing with every series, shown when searching without a query
+	pub catalogue_listing: Cow<'static, str>,
+	/// Selectors for listings the site doesn't handle itself
+	pub catalogue_selectors: ListSelectors,
+	/// Link to the next page of search results
+	pub search_next_page_selector: Cow<'static, str>,
+	/// Home sections in display order, sections without entries are left out
+	pub home_sections: Cow<'static, [HomeSection]>,
+	/// Page listing ongoing series by weekday, for the `weekday/<day>` listings
+	pub weekday_path: Cow<'static, str>,
+	/// Selectors for the series of one weekday, `{weekday}` in `item` becomes e.g. `monday`
+	pub weekday_selectors: ListSelectors,
+	/// Page listing completed series, for the `completed` listing
+	pub completed_path: Cow<'static, str>,
+	/// Page listing magazine issues, for the `magazine` listing
+	pub magazine_path: Cow<'static, str>,
+	/// Page listing volumes, for the `volume` listing
+	pub volume_path: Cow<'static, str>,
+	/// Selectors for the magazine issue and volume listings
+	pub product_selectors: ListSelectors,
 }
 
 impl Default for Params {
@@ -31,6 +112,30 @@ impl Default for Params {
 			is_paginated: false,
 			popular_item_selector: "ul.series-list li a".into(),
 			chapter_list_selector: "li.episode".into(),
+			catalogue_listing: "series".into(),
+			catalogue_selectors: ListSelectors {
+				item: ".series-list-item".into(),
+				title: ".series-list-title".into(),
+				cover: ".series-list-thumb img".into(),
+				cover_attr: "data-src".into(),
+				authors: Some(".series-list-author".into()),
+				description: None,
+			},
+			search_next_page_selector: ".pagination .next a, a[rel=next]".into(),
+			home_sections: Cow::Owned(home::default_sections()),
+			weekday_path: "series".into(),
+			weekday_selectors: weekday::default_selectors(),
+			completed_path: "series/finished".into(),
+			magazine_path: "magazine".into(),
+			volume_path: "volume".into(),
+			product_selectors: ListSelectors {
+				item: ".magazine-list-item, .volume-list-item".into(),
+				title: ".magazine-list-title, .volume-list-title".into(),
+				cover: "img".into(),
+				cover_attr: "data-src".into(),
+				authors: None,
+				description: None,
+			},
 		}
 	}
 }
@@ -0,0 +1,34 @@
+/// Page of the site a shared link points to, with its path (ex: `/episode/<id>`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GigaLink<'a> {
+	Episode(&'a str),
+	Series(&'a str),
+	Magazine(&'a str),
+	Volume(&'a str),
+}
+
+/// Parse a link to one of the site's pages, ignoring the query, fragment and trailing slash
+pub fn parse_link<'a>(url: &'a str, base_url: &str) -> Option<GigaLink<'a>> {
+	let path = url.strip_prefix(base_url)?;
+	if !path.starts_with('/') {
+		return None;
+	}
+	let path = path.split(['?', '#']).next().unwrap_or_default();
+	let path = path.trim_end_matches('/');
+
+	let (kind, id) = path.trim_start_matches('/').split_once('/')?;
+	if id.is_empty() || id.contains('/') {
+		return None;
+	}
+
+	match kind {
+		"episode" => Some(GigaLink::Episode(path)),
+		"series" => Some(GigaLink::Series(path)),
+		"magazine" => Some(GigaLink::Magazine(path)),
+		"volume" => Some(GigaLink::Volume(path)),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+use super::*;
+use aidoku_test::aidoku_test;
+
+const BASE_URL: &str = "https://shonenjumpplus.com";
+
+#[aidoku_test]
+fn link_kinds() {
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/episode/10834108156648240735",
+			BASE_URL
+		),
+		Some(GigaLink::Episode("/episode/10834108156648240735"))
+	);
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/series/10834108156766380123",
+			BASE_URL
+		),
+		Some(GigaLink::Series("/series/10834108156766380123"))
+	);
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/magazine/10834108156649174891",
+			BASE_URL
+		),
+		Some(GigaLink::Magazine("/magazine/10834108156649174891"))
+	);
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/volume/3270375685339998456",
+			BASE_URL
+		),
+		Some(GigaLink::Volume("/volume/3270375685339998456"))
+	);
+}
+
+#[aidoku_test]
+fn query_fragment_and_trailing_slash() {
+	let expected = Some(GigaLink::Episode("/episode/123"));
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/episode/123?utm_source=x",
+			BASE_URL
+		),
+		expected
+	);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com/episode/123/", BASE_URL),
+		expected
+	);
+	assert_eq!(
+		parse_link(
+			"https://shonenjumpplus.com/episode/123/?from=share#top",
+			BASE_URL
+		),
+		expected
+	);
+}
+
+#[aidoku_test]
+fn unknown_links() {
+	assert_eq!(parse_link("https://shonenjumpplus.com/", BASE_URL), None);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com/series", BASE_URL),
+		None
+	);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com/episode/", BASE_URL),
+		None
+	);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com/ranking/daily", BASE_URL),
+		None
+	);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com/episode/123/comments", BASE_URL),
+		None
+	);
+	assert_eq!(
+		parse_link("https://shonenjumpplus.com.evil/episode/123", BASE_URL),
+		None
+	);
+	assert_eq!(
+		parse_link("https://comic-days.com/episode/123", BASE_URL),
+		None
+	);
+}
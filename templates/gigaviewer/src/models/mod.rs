@@ -0,0 +1,201 @@
+use aidoku::{
+	alloc::{format, String, Vec},
+	imports::std::parse_date,
+	Chapter,
+};
+use serde::Deserialize;
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GigaEpisode {
+	pub readable_product: GigaReadableProduct,
+}
+
+impl GigaEpisode {
+	pub fn has_main_pages(&self) -> bool {
+		self.readable_product
+			.page_structure
+			.pages
+			.iter()
+			.any(|page| page.r#type.as_deref() == Some("main"))
+	}
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GigaReadableProduct {
+	pub title: Option<String>,
+	pub page_structure: GigaPageStructure,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GigaPageStructure {
+	pub pages: Vec<GigaPage>,
+	/// Scrambling method, `baku` for shuffled tiles
+	pub cho_ju_giga: Option<String>,
+	/// Tile grid, when it differs from the usual 4×4 tiles of multiples of 8 pixels
+	pub divide_num: Option<i32>,
+	pub multiple: Option<i32>,
+}
+
+impl GigaPageStructure {
+	/// Pages are only shuffled when the method is unknown or `baku`
+	pub fn is_scrambled(&self) -> bool {
+		self.cho_ju_giga
+			.as_deref()
+			.is_none_or(|method| method == "baku")
+	}
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GigaPage {
+	pub src: Option<String>,
+	pub r#type: Option<String>,
+	pub width: Option<i32>,
+	pub height: Option<i32>,
+	/// Set on pages served without scrambling
+	pub is_scrambled: Option<bool>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GigaReadMoreResponse {
+	pub html: String,
+	pub next_url: String,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+pub struct GigaPaginationReadableProduct {
+	pub display_open_at: Option<String>,
+	pub readable_product_id: Option<String>,
+	pub status: Option<GigaPaginationReadableProductStatus>,
+	pub thumbnail_uri: Option<String>,
+	pub title: Option<String>,
+	pub viewer_uri: Option<String>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+pub struct GigaPaginationReadableProductStatus {
+	pub label: Option<String>, // is_free, is_rentable, is_purchasable, unpublished, has_rented, has_purchased
+	pub rental_price: Option<i32>,
+	pub buy_price: Option<i32>,
+}
+
+/// Whether an episode can be read by the current (possibly logged in) user
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpisodeAccess {
+	Free,
+	/// Normally paid but free for now (無料キャンペーン)
+	FreeToday,
+	Rented,
+	Purchased,
+	/// Price in points, when known
+	Rentable(Option<i32>),
+	Purchasable(Option<i32>),
+	Unpublished,
+	/// Unknown status, treated as not readable
+	Locked,
+}
+
+impl EpisodeAccess {
+	pub fn from_status(status: &GigaPaginationReadableProductStatus) -> Self {
+		match status.label.as_deref() {
+			Some("is_free") => Self::Free,
+			Some("has_rented") => Self::Rented,
+			Some("has_purchased") => Self::Purchased,
+			Some("is_rentable") => Self::Rentable(status.rental_price),
+			Some("is_purchasable") => Self::Purchasable(status.buy_price),
+			Some("unpublished") => Self::Unpublished,
+			_ => Self::Locked,
+		}
+	}
+
+	/// From the text of an episode list item and its price label, if any
+	pub fn from_list_item(text: &str, price: Option<&str>) -> Self {
+		if text.contains("レンタル中") {
+			return Self::Rented;
+		}
+		if text.contains("購入済") {
+			return Self::Purchased;
+		}
+		let Some(price) = price else {
+			return Self::Free;
+		};
+		if price.contains("無料") {
+			return Self::FreeToday;
+		}
+		let points = price
+			.chars()
+			.filter(char::is_ascii_digit)
+			.collect::<String>()
+			.parse()
+			.ok();
+		// episodes without a rental price can only be bought
+		if price.contains("購入") && !price.contains("レンタル") {
+			Self::Purchasable(points)
+		} else {
+			Self::Rentable(points)
+		}
+	}
+
+	pub fn is_locked(self) -> bool {
+		matches!(
+			self,
+			Self::Rentable(_) | Self::Purchasable(_) | Self::Unpublished | Self::Locked
+		)
+	}
+
+	/// Shown after the chapter title
+	pub fn label(self) -> Option<String> {
+		match self {
+			Self::Free | Self::Locked => None,
+			Self::FreeToday => Some("無料".into()),
+			Self::Rented => Some("レンタル中".into()),
+			Self::Purchased => Some("購入済み".into()),
+			Self::Rentable(Some(price)) => Some(format!("レンタル {price}pt")),
+			Self::Rentable(None) => Some("レンタル".into()),
+			Self::Purchasable(Some(price)) => Some(format!("購入 {price}pt")),
+			Self::Purchasable(None) => Some("購入".into()),
+			Self::Unpublished => Some("公開前".into()),
+		}
+	}
+
+	/// Title with the access label appended, e.g. `第1話【レンタル中】`
+	pub fn decorate_title(self, title: Option<String>) -> Option<String> {
+		match (title, self.label()) {
+			(Some(title), Some(label)) => Some(format!("{title}【{label}】")),
+			(title, _) => title,
+		}
+	}
+}
+
+impl From<GigaPaginationReadableProduct> for Chapter {
+	fn from(val: GigaPaginationReadableProduct) -> Self {
+		let chapter_number = val
+			.title
+			.clone()
+			.and_then(crate::parser::parse_chapter_number);
+		let access = val
+			.status
+			.as_ref()
+			.map(EpisodeAccess::from_status)
+			.unwrap_or(EpisodeAccess::Free);
+		Chapter {
+			key: format!("/episode/{}", val.readable_product_id.unwrap_or_default()),
+			title: access.decorate_title(val.title),
+			chapter_number,
+			date_uploaded: val
+				.display_open_at
+				.and_then(|str| parse_date(str, "yyyy-MM-dd'T'HH:mm:ss'Z'")),
+			url: val.viewer_uri,
+			thumbnail: val.thumbnail_uri,
+			locked: access.is_locked(),
+			..Default::default()
+		}
+	}
+}
+
+#[cfg(test)]
+mod test;
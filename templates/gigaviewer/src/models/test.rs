@@ -0,0 +1,121 @@
+use super::*;
+use aidoku::alloc::string::ToString;
+use aidoku_test::aidoku_test;
+
+fn status(label: Option<&str>) -> GigaPaginationReadableProductStatus {
+	GigaPaginationReadableProductStatus {
+		label: label.map(String::from),
+		rental_price: Some(50),
+		buy_price: Some(80),
+	}
+}
+
+#[aidoku_test]
+fn access_from_status() {
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("is_free"))),
+		EpisodeAccess::Free
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("has_rented"))),
+		EpisodeAccess::Rented
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("has_purchased"))),
+		EpisodeAccess::Purchased
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("is_rentable"))),
+		EpisodeAccess::Rentable(Some(50))
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("is_purchasable"))),
+		EpisodeAccess::Purchasable(Some(80))
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("unpublished"))),
+		EpisodeAccess::Unpublished
+	);
+	assert_eq!(
+		EpisodeAccess::from_status(&status(Some("is_something_new"))),
+		EpisodeAccess::Locked
+	);
+}
+
+#[aidoku_test]
+fn missing_status_stays_readable() {
+	let chapter: Chapter = GigaPaginationReadableProduct {
+		readable_product_id: Some("10834108156648240735".to_string()),
+		title: Some("第1話".to_string()),
+		..Default::default()
+	}
+	.into();
+	assert!(!chapter.locked);
+	assert_eq!(chapter.title.as_deref(), Some("第1話"));
+}
+
+#[aidoku_test]
+fn access_from_list_item() {
+	assert_eq!(
+		EpisodeAccess::from_list_item("第1話", None),
+		EpisodeAccess::Free
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第2話 無料", Some("無料")),
+		EpisodeAccess::FreeToday
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第3話 レンタル中", Some("50")),
+		EpisodeAccess::Rented
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第4話 購入済", Some("80")),
+		EpisodeAccess::Purchased
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第5話", Some("レンタル 50pt")),
+		EpisodeAccess::Rentable(Some(50))
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第6話", Some("購入 80pt")),
+		EpisodeAccess::Purchasable(Some(80))
+	);
+	assert_eq!(
+		EpisodeAccess::from_list_item("第7話", Some("ポイント")),
+		EpisodeAccess::Rentable(None)
+	);
+}
+
+#[aidoku_test]
+fn locked_states() {
+	assert!(!EpisodeAccess::Free.is_locked());
+	assert!(!EpisodeAccess::FreeToday.is_locked());
+	assert!(!EpisodeAccess::Rented.is_locked());
+	assert!(!EpisodeAccess::Purchased.is_locked());
+	assert!(EpisodeAccess::Rentable(None).is_locked());
+	assert!(EpisodeAccess::Purchasable(None).is_locked());
+	assert!(EpisodeAccess::Unpublished.is_locked());
+	assert!(EpisodeAccess::Locked.is_locked());
+}
+
+#[aidoku_test]
+fn decorated_titles() {
+	let title = || Some("第1話".to_string());
+	assert_eq!(
+		EpisodeAccess::Free.decorate_title(title()),
+		Some("第1話".to_string())
+	);
+	assert_eq!(
+		EpisodeAccess::FreeToday.decorate_title(title()),
+		Some("第1話【無料】".to_string())
+	);
+	assert_eq!(
+		EpisodeAccess::Rentable(Some(50)).decorate_title(title()),
+		Some("第1話【レンタル 50pt】".to_string())
+	);
+	assert_eq!(
+		EpisodeAccess::Purchasable(None).decorate_title(title()),
+		Some("第1話【購入】".to_string())
+	);
+	assert_eq!(EpisodeAccess::Rented.decorate_title(None), None);
+}
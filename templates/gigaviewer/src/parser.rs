@@ -12,7 +12,7 @@ use aidoku::{
 };
 
 use crate::{
-	models::{GigaPaginationReadableProduct, GigaReadMoreResponse},
+	models::{EpisodeAccess, GigaPaginationReadableProduct, GigaReadMoreResponse},
 	AuthedRequest,
 };
 
@@ -70,14 +70,7 @@ pub fn parse_chapters_single_page(
 	chapter_list_selector: &str,
 ) -> Result<Vec<Chapter>> {
 	let target_endpoint = {
-		let aggregate_id = html
-			.select_first("script.js-valve")
-			.and_then(|el| el.attr("data-giga_series"))
-			.unwrap_or_else(|| {
-				html.select_first(".readable-products-pagination")
-					.and_then(|el| el.attr("data-aggregate-id"))
-					.unwrap_or_default()
-			});
+		let aggregate_id = parse_aggregate_id(html).unwrap_or_default();
 
 		let mut qs = QueryParameters::new();
 		qs.push("aggregate_id", Some(&aggregate_id));
@@ -91,8 +84,7 @@ pub fn parse_chapters_single_page(
 
 	let mut json = Request::get(target_endpoint)?
 		.header("Referer", referer_url)
-		.authed()
-		.json_owned::<GigaReadMoreResponse>();
+		.json_authed::<GigaReadMoreResponse>();
 	let mut chapters: Vec<Chapter> = Vec::new();
 
 	while let Ok(ok_json) = json {
@@ -103,8 +95,7 @@ pub fn parse_chapters_single_page(
 		}
 		json = Request::get(ok_json.next_url)?
 			.header("Referer", referer_url)
-			.authed()
-			.json_owned::<GigaReadMoreResponse>();
+			.json_authed::<GigaReadMoreResponse>();
 	}
 
 	Ok(chapters)
@@ -115,14 +106,7 @@ pub fn parse_chapters_paginated(
 	base_url: &str,
 	referer_url: &str,
 ) -> Result<Vec<Chapter>> {
-	let aggregate_id = html
-		.select_first("script.js-valve")
-		.and_then(|el| el.attr("data-giga_series"))
-		.unwrap_or_else(|| {
-			html.select_first(".readable-products-pagination")
-				.and_then(|el| el.attr("data-aggregate-id"))
-				.unwrap_or_default()
-		});
+	let aggregate_id = parse_aggregate_id(html).unwrap_or_default();
 
 	let mut chapters = Vec::new();
 	let mut offset = 0;
@@ -139,8 +123,7 @@ pub fn parse_chapters_paginated(
 		};
 		let json = Request::get(url)?
 			.header("Referer", referer_url)
-			.authed()
-			.json_owned::<Vec<GigaPaginationReadableProduct>>()
+			.json_authed::<Vec<GigaPaginationReadableProduct>>()
 			.unwrap_or_default();
 
 		if json.is_empty() {
@@ -154,6 +137,45 @@ pub fn parse_chapters_paginated(
 	Ok(chapters)
 }
 
+/// Series id of an episode or series page, used by the viewer APIs
+pub fn parse_aggregate_id(html: &Document) -> Option<String> {
+	html.select_first("script.js-valve")
+		.and_then(|el| el.attr("data-giga_series"))
+		.or_else(|| {
+			html.select_first(".readable-products-pagination")
+				.and_then(|el| el.attr("data-aggregate-id"))
+		})
+		.filter(|id| !id.is_empty())
+}
+
+/// Key of the first episode of a series, which series lists link to
+pub fn first_episode_key(base_url: &str, aggregate_id: &str) -> Result<Option<String>> {
+	let mut qs = QueryParameters::new();
+	qs.push("type", Some("episode"));
+	qs.push("aggregate_id", Some(aggregate_id));
+	qs.push("sort_order", Some("asc"));
+	qs.push("offset", Some("0"));
+	let url = format!("{base_url}/api/viewer/pagination_readable_products?{qs}");
+
+	let first = Request::get(url)?
+		.header("Referer", base_url)
+		.json_authed::<Vec<GigaPaginationReadableProduct>>()
+		.unwrap_or_default()
+		.into_iter()
+		.next();
+
+	Ok(first.and_then(|product| {
+		product
+			.viewer_uri
+			.and_then(|uri| uri.strip_prefix(base_url).map(String::from))
+			.or_else(|| {
+				product
+					.readable_product_id
+					.map(|id| format!("/episode/{id}"))
+			})
+	}))
+}
+
 pub fn parse_chapter_elements(
 	html: String,
 	base_url: &str,
@@ -171,7 +193,14 @@ pub fn parse_chapter_elements(
 						.select_first("span.series-episode-list-date")
 						.and_then(|e| parse_local_date(e.text()?, "yyyy/MM/dd"));
 
-					let locked = e.select_first(".series-episode-list-price").is_some();
+					let price = e
+						.select_first(".series-episode-list-price")
+						.map(|e| e.text().unwrap_or_default());
+					let access = EpisodeAccess::from_list_item(
+						&e.text().unwrap_or_default(),
+						price.as_deref(),
+					);
+					let locked = access.is_locked();
 
 					if skip_locked && locked {
 						return None;
@@ -190,6 +219,7 @@ pub fn parse_chapter_elements(
 						.select_first("h4.series-episode-list-title")
 						.and_then(|e| e.text());
 					let chapter_number = title.clone().and_then(parse_chapter_number);
+					let title = access.decorate_title(title);
 					let thumbnail = info
 						.select_first(".series-episode-list-thumb-container img")
 						.and_then(|e| e.attr("src"));
@@ -211,7 +241,10 @@ pub fn parse_chapter_elements(
 				let only_chapter_has_manga_title = chapters[0]
 					.title
 					.as_ref()
-					.map(|str| str == manga_title)
+					.map(|str| {
+						// the title may carry an access label, e.g. `タイトル【無料】`
+						str == manga_title || str.starts_with(&format!("{manga_title}【"))
+					})
 					.unwrap_or(false);
 				if only_chapter_has_manga_title {
 					chapters[0].chapter_number = Some(1.0);
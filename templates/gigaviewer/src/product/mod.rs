@@ -0,0 +1,78 @@
+use aidoku::{
+	alloc::{String, Vec},
+	imports::{html::Document, net::Request},
+	prelude::*,
+	Chapter, Manga, Result, Viewer,
+};
+
+use crate::{models::GigaEpisode, AuthedRequest, Params};
+
+/// Magazine issues and volumes are read like episodes, but aren't part of a series page
+pub fn is_readable_product_key(key: &str) -> bool {
+	key.starts_with("/magazine/") || key.starts_with("/volume/")
+}
+
+/// A magazine issue or volume is an entry with itself as the only chapter
+pub fn get_readable_product_update(
+	params: &Params,
+	manga: Manga,
+	needs_details: bool,
+	needs_chapters: bool,
+) -> Result<Manga> {
+	let mut new_manga = manga.clone();
+
+	let url = format!("{}{}", params.base_url, manga.key);
+	let html = Request::get(&url)?.html_authed()?;
+	let (details, chapter) = parse_readable_product(&html, &manga, &url);
+
+	if needs_details {
+		new_manga.copy_from(details);
+	}
+	if needs_chapters {
+		new_manga.chapters = Some(Vec::from([chapter]));
+	}
+
+	Ok(new_manga)
+}
+
+/// Details and the only chapter of a magazine issue or volume page
+fn parse_readable_product(html: &Document, manga: &Manga, url: &str) -> (Manga, Chapter) {
+	let episode = html
+		.select_first("script#episode-json")
+		.and_then(|e| e.attr("data-value"))
+		.and_then(|v| serde_json::from_str::<GigaEpisode>(v.as_ref()).ok());
+	let meta = |property: &str| {
+		html.select_first(format!("meta[property='{property}']"))
+			.and_then(|e| e.attr("content"))
+	};
+	let title = episode
+		.as_ref()
+		.and_then(|episode| episode.readable_product.title.clone())
+		.or_else(|| meta("og:title"))
+		.unwrap_or(manga.title.clone());
+
+	let details = Manga {
+		key: manga.key.clone(),
+		title: title.clone(),
+		cover: meta("og:image"),
+		description: meta("og:description"),
+		url: Some(url.into()),
+		viewer: Viewer::RightToLeft,
+		..Default::default()
+	};
+	let chapter = Chapter {
+		key: manga.key.clone(),
+		title: Some(title),
+		chapter_number: Some(1.0),
+		url: Some(String::from(url)),
+		thumbnail: meta("og:image"),
+		// pages are only listed for issues that were bought or rented
+		locked: !episode.is_some_and(|episode| episode.has_main_pages()),
+		..Default::default()
+	};
+
+	(details, chapter)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,83 @@
+use super::*;
+use aidoku::imports::html::Html;
+use aidoku_test::aidoku_test;
+
+const BASE_URL: &str = "https://shonenjumpplus.com";
+
+fn parse(html: &str) -> Document {
+	Html::parse(String::from(html)).expect("valid html")
+}
+
+fn volume() -> Manga {
+	Manga {
+		key: "/volume/3270375685339998456".into(),
+		title: "SPY×FAMILY 1".into(),
+		..Default::default()
+	}
+}
+
+#[aidoku_test]
+fn product_keys() {
+	assert!(is_readable_product_key("/magazine/10834108156649174891"));
+	assert!(is_readable_product_key("/volume/3270375685339998456"));
+	assert!(!is_readable_product_key("/episode/10834108156648240735"));
+	assert!(!is_readable_product_key("/series/10834108156766380123"));
+	assert!(!is_readable_product_key("/magazines"));
+}
+
+#[aidoku_test]
+fn listing_page() {
+	let html = parse(include_str!("fixtures/magazine_list.html"));
+	let entries = Params::default().product_selectors.parse(&html, BASE_URL);
+
+	let keys: Vec<&str> = entries.iter().map(|manga| manga.key.as_str()).collect();
+	assert_eq!(
+		keys,
+		[
+			"/magazine/10834108156649174891",
+			"/magazine/10834108156649174892",
+			"/volume/3270375685339998456",
+		]
+	);
+	assert_eq!(entries[0].title, "週刊少年ジャンプ 2024年1号");
+	assert_eq!(
+		entries[2].cover.as_deref(),
+		Some("https://cdn-ak-img.shonenjumpplus.com/public/volume/3270375685339998456/cover.jpg")
+	);
+	assert!(entries
+		.iter()
+		.all(|manga| is_readable_product_key(&manga.key)));
+}
+
+#[aidoku_test]
+fn readable_product_page() {
+	let html = parse(include_str!("fixtures/volume.html"));
+	let url = "https://shonenjumpplus.com/volume/3270375685339998456";
+	let (details, chapter) = parse_readable_product(&html, &volume(), url);
+
+	assert_eq!(details.title, "SPY×FAMILY 1");
+	assert_eq!(
+		details.cover.as_deref(),
+		Some("https://cdn-ak-img.shonenjumpplus.com/public/volume/3270375685339998456/og.jpg")
+	);
+	assert_eq!(
+		details.description.as_deref(),
+		Some("凄腕スパイの〈黄昏〉が、任務のために家族を作る！")
+	);
+	assert_eq!(chapter.key, "/volume/3270375685339998456");
+	assert_eq!(chapter.url.as_deref(), Some(url));
+	assert!(!chapter.locked);
+}
+
+#[aidoku_test]
+fn product_without_pages_is_locked() {
+	let html = parse(
+		r#"<html><head><meta property="og:title" content="SPY×FAMILY 2 | 少年ジャンプ＋"></head>
+		<body></body></html>"#,
+	);
+	let (details, chapter) = parse_readable_product(&html, &volume(), "");
+
+	// without the episode JSON the page title is used
+	assert_eq!(details.title, "SPY×FAMILY 2 | 少年ジャンプ＋");
+	assert!(chapter.locked);
+}
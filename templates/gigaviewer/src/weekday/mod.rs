@@ -0,0 +1,91 @@
+use aidoku::imports::std::current_date;
+
+use crate::ListSelectors;
+
+/// Placeholder in selectors for the id of a weekday
+pub const WEEKDAY_PLACEHOLDER: &str = "{weekday}";
+
+/// Selectors for the series of one weekday on most GigaViewer series pages
+pub fn default_selectors() -> ListSelectors {
+	ListSelectors {
+		item: ".js-daily-series[data-day-of-week='{weekday}'] .series-list-item, \
+		       .daily-{weekday} .daily-series-item"
+			.into(),
+		title: ".series-list-title, .daily-series-title".into(),
+		cover: ".series-list-thumb img, .daily-series-thumb img".into(),
+		cover_attr: "data-src".into(),
+		authors: Some(".series-list-author, .daily-series-author".into()),
+		description: None,
+	}
+}
+
+/// Day of the week a series is serialized on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+	Monday,
+	Tuesday,
+	Wednesday,
+	Thursday,
+	Friday,
+	Saturday,
+	Sunday,
+}
+
+impl Weekday {
+	pub const ALL: [Self; 7] = [
+		Self::Monday,
+		Self::Tuesday,
+		Self::Wednesday,
+		Self::Thursday,
+		Self::Friday,
+		Self::Saturday,
+		Self::Sunday,
+	];
+
+	/// Replaces `{weekday}` in `Params::weekday_selectors`
+	pub const fn id(self) -> &'static str {
+		match self {
+			Self::Monday => "monday",
+			Self::Tuesday => "tuesday",
+			Self::Wednesday => "wednesday",
+			Self::Thursday => "thursday",
+			Self::Friday => "friday",
+			Self::Saturday => "saturday",
+			Self::Sunday => "sunday",
+		}
+	}
+
+	/// Selectors with `{weekday}` in `item` replaced by this weekday
+	pub fn fill_selectors(self, selectors: &ListSelectors) -> ListSelectors {
+		ListSelectors {
+			item: selectors
+				.item
+				.replace(WEEKDAY_PLACEHOLDER, self.id())
+				.into(),
+			..selectors.clone()
+		}
+	}
+
+	/// Weekday listing ids are `weekday/<id>`, or `weekday/today`
+	pub fn from_listing_id(id: &str) -> Option<Self> {
+		match id.strip_prefix("weekday/")? {
+			"today" => Some(Self::today()),
+			day => Self::ALL.into_iter().find(|weekday| weekday.id() == day),
+		}
+	}
+
+	/// Today in Japan, where the sites update
+	pub fn today() -> Self {
+		Self::from_timestamp(current_date())
+	}
+
+	fn from_timestamp(timestamp: i64) -> Self {
+		const JST_OFFSET: i64 = 9 * 60 * 60;
+		let days = (timestamp + JST_OFFSET).div_euclid(24 * 60 * 60);
+		// 1970-01-01 was a Thursday
+		Self::ALL[(days + 3).rem_euclid(7) as usize]
+	}
+}
+
+#[cfg(test)]
+mod test;
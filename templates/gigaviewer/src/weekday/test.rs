@@ -0,0 +1,60 @@
+use super::*;
+use aidoku::prelude::*;
+use aidoku_test::aidoku_test;
+
+/// 2024-01-01 00:00 in Japan, a Monday
+const MONDAY_JST: i64 = 1_704_034_800;
+const DAY: i64 = 24 * 60 * 60;
+
+#[aidoku_test]
+fn listing_ids() {
+	assert_eq!(
+		Weekday::from_listing_id("weekday/monday"),
+		Some(Weekday::Monday)
+	);
+	assert_eq!(
+		Weekday::from_listing_id("weekday/sunday"),
+		Some(Weekday::Sunday)
+	);
+	assert_eq!(
+		Weekday::from_listing_id("weekday/today"),
+		Some(Weekday::today())
+	);
+	assert_eq!(Weekday::from_listing_id("weekday/someday"), None);
+	assert_eq!(Weekday::from_listing_id("monday"), None);
+	assert_eq!(Weekday::from_listing_id("series/finished"), None);
+}
+
+#[aidoku_test]
+fn ids_round_trip() {
+	for weekday in Weekday::ALL {
+		let id = format!("weekday/{}", weekday.id());
+		assert_eq!(Weekday::from_listing_id(&id), Some(weekday));
+	}
+}
+
+#[aidoku_test]
+fn days_follow_japan_time() {
+	// 1970-01-01 was a Thursday, in UTC and in Japan
+	assert_eq!(Weekday::from_timestamp(0), Weekday::Thursday);
+	// Friday starts at 15:00 UTC in Japan
+	assert_eq!(Weekday::from_timestamp(15 * 60 * 60 - 1), Weekday::Thursday);
+	assert_eq!(Weekday::from_timestamp(15 * 60 * 60), Weekday::Friday);
+
+	assert_eq!(Weekday::from_timestamp(MONDAY_JST - 1), Weekday::Sunday);
+	for (offset, weekday) in Weekday::ALL.into_iter().enumerate() {
+		let start = MONDAY_JST + offset as i64 * DAY;
+		assert_eq!(Weekday::from_timestamp(start), weekday);
+		assert_eq!(Weekday::from_timestamp(start + DAY - 1), weekday);
+	}
+}
+
+#[aidoku_test]
+fn days_before_epoch() {
+	assert_eq!(Weekday::from_timestamp(-9 * 60 * 60), Weekday::Thursday);
+	assert_eq!(
+		Weekday::from_timestamp(-9 * 60 * 60 - 1),
+		Weekday::Wednesday
+	);
+	assert_eq!(Weekday::from_timestamp(-7 * DAY), Weekday::Thursday);
+}
@@ -1,6 +1,6 @@
 use aidoku::{
 	AidokuError, Result,
-	alloc::{String, string::ToString},
+	alloc::{String, Vec, string::ToString},
 	imports::{
 		defaults::{DefaultValue, defaults_get, defaults_get_json, defaults_set},
 		net::{Request, Response},
@@ -11,7 +11,10 @@ use aidoku::{
 use crate::{
 	context::Context,
 	endpoints::Url,
-	models::responses::{TokenResponse, UserResponse},
+	models::{
+		chapter::LockReason,
+		responses::{TokenResponse, UserResponse},
+	},
 	settings::get_api_url,
 };
 
@@ -30,12 +33,20 @@ const HEADER_CONTENT_TYPE: &str = "Content-Type";
 const CONTENT_TYPE_FORM: &str = "application/x-www-form-urlencoded";
 const AUTH_SCHEME: &str = "Bearer";
 
+/// 451 Unavailable For Legal Reasons, which the API answers with
+/// when 18+ content is requested by an anonymous or unverified account
+const STATUS_AGE_RESTRICTED: i32 = 451;
+
 pub trait AuthRequest {
+	/// Add the site headers and the stored token without sending.
+	/// Unlike `authed`, an expired token isn't refreshed, see `send_all_authed`.
+	fn with_auth_headers(self, ctx: &Context) -> Request;
+
 	fn authed(self, ctx: &Context) -> Result<Response>;
 }
 
 impl AuthRequest for Request {
-	fn authed(mut self, ctx: &Context) -> Result<Response> {
+	fn with_auth_headers(mut self, ctx: &Context) -> Request {
 		self = self
 			.header("Origin", &ctx.base_url)
 			.header("Referer", &ctx.api_url)
@@ -48,7 +59,11 @@ impl AuthRequest for Request {
 			self = self.header(HEADER_AUTH, &format!("{AUTH_SCHEME} {access_token}"));
 		}
 
-		let response = self.send()?;
+		self
+	}
+
+	fn authed(self, ctx: &Context) -> Result<Response> {
+		let mut response = self.with_auth_headers(ctx).send()?;
 
 		// Try refresh and retry once
 		if response.status_code() == 401
@@ -56,26 +71,63 @@ impl AuthRequest for Request {
 			&& let Ok(new_token) = get_token()
 			&& let Some(access_token) = new_token.access_token
 		{
-			return Ok(response
+			response = response
 				.into_request()
 				.header("Origin", &ctx.base_url)
 				.header("Referer", &ctx.api_url)
 				.header("Site-Id", &ctx.site_id.to_string())
 				.header("User-Agent", USER_AGENT)
 				.header(HEADER_AUTH, &format!("{AUTH_SCHEME} {access_token}"))
-				.send()?);
+				.send()?;
 		}
 
+		reject_age_restricted(response)
+	}
+}
+
+/// Turn the 18+ rejection into an error explaining how to unlock the title
+fn reject_age_restricted(response: Response) -> Result<Response> {
+	if response.status_code() == STATUS_AGE_RESTRICTED {
+		Err(AidokuError::message(LockReason::AgeRestricted.message()))
+	} else {
 		Ok(response)
 	}
 }
 
+/// Send requests at once, retrying through `authed` the ones rejected with an expired token.
+pub fn send_all_authed(
+	requests: impl IntoIterator<Item = Request>,
+	ctx: &Context,
+) -> Vec<Result<Response>> {
+	let requests: Vec<Request> = requests
+		.into_iter()
+		.map(|request| request.with_auth_headers(ctx))
+		.collect();
+
+	Request::send_all(requests)
+		.into_iter()
+		.map(|response| {
+			let response = response?;
+			if response.status_code() == 401 {
+				response.into_request().authed(ctx)
+			} else {
+				reject_age_restricted(response)
+			}
+		})
+		.collect()
+}
+
 /// Retrieves the stored authentication token from defaults.
 fn get_token() -> Result<TokenResponse> {
 	defaults_get_json::<TokenResponse>(TOKEN_KEY)
 		.map_err(|_| AidokuError::Message("No token".into()))
 }
 
+/// Returns true if an access token is stored.
+pub fn is_logged_in() -> bool {
+	get_token().is_ok_and(|token| token.access_token.is_some())
+}
+
 /// Retrieves the stored user ID from defaults.
 /// If no user ID is stored but we have a valid token, fetches and stores it automatically.
 pub fn get_user_id(ctx: &Context) -> Option<i32> {
@@ -0,0 +1,91 @@
+use aidoku::{
+	Listing, ListingKind,
+	alloc::{String, Vec, string::ToString},
+	prelude::*,
+};
+
+/// Prefix for bookmark folder listing ids
+pub const BOOKMARKS_LISTING_PREFIX: &str = "bookmarks-";
+
+/// SocialLib bookmark folders shared by all LibGroup sites
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookmarkFolder {
+	Reading,
+	Planned,
+	Completed,
+	Dropped,
+	Favorite,
+}
+
+impl BookmarkFolder {
+	/// Folders in the order the site shows them
+	pub const ALL: [Self; 5] = [
+		Self::Reading,
+		Self::Planned,
+		Self::Completed,
+		Self::Dropped,
+		Self::Favorite,
+	];
+
+	/// Bookmark status id used by the `bookmarks[]` catalog parameter
+	pub const fn status(self) -> u8 {
+		match self {
+			Self::Reading => 1,
+			Self::Planned => 2,
+			Self::Dropped => 3,
+			Self::Completed => 4,
+			Self::Favorite => 5,
+		}
+	}
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Reading => "Читаю",
+			Self::Planned => "В планах",
+			Self::Completed => "Прочитано",
+			Self::Dropped => "Брошено",
+			Self::Favorite => "Любимые",
+		}
+	}
+
+	pub fn from_status(status: u8) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|folder| folder.status() == status)
+	}
+
+	/// Parse a folder from a listing id like `bookmarks-1`
+	pub fn from_listing_id(id: &str) -> Option<Self> {
+		id.strip_prefix(BOOKMARKS_LISTING_PREFIX)
+			.and_then(|status| status.parse::<u8>().ok())
+			.and_then(Self::from_status)
+	}
+
+	pub fn listing_id(self) -> String {
+		format!("{BOOKMARKS_LISTING_PREFIX}{}", self.status())
+	}
+
+	pub fn into_listing(self) -> Listing {
+		Listing {
+			id: self.listing_id(),
+			name: format!("Закладки: {}", self.name()),
+			kind: ListingKind::Default,
+		}
+	}
+
+	/// Query parameters selecting this folder in the catalog search
+	pub fn query_params(self) -> Vec<(&'static str, String)> {
+		Vec::from([("bookmarks[]", self.status().to_string())])
+	}
+}
+
+/// Listings for every bookmark folder
+pub fn bookmark_listings() -> Vec<Listing> {
+	BookmarkFolder::ALL
+		.into_iter()
+		.map(BookmarkFolder::into_listing)
+		.collect()
+}
+
+#[cfg(test)]
+mod test;
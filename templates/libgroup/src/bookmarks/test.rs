@@ -0,0 +1,43 @@
+use super::*;
+use aidoku_test::aidoku_test;
+
+#[aidoku_test]
+fn listing_id_round_trip() {
+	for folder in BookmarkFolder::ALL {
+		let id = folder.listing_id();
+		assert!(id.starts_with(BOOKMARKS_LISTING_PREFIX));
+		assert_eq!(BookmarkFolder::from_listing_id(&id), Some(folder));
+	}
+}
+
+#[aidoku_test]
+fn unknown_listing_ids_are_rejected() {
+	assert_eq!(BookmarkFolder::from_listing_id("popular"), None);
+	assert_eq!(BookmarkFolder::from_listing_id("bookmarks-"), None);
+	assert_eq!(BookmarkFolder::from_listing_id("bookmarks-9"), None);
+	assert_eq!(BookmarkFolder::from_listing_id("bookmarks-abc"), None);
+}
+
+#[aidoku_test]
+fn listings_follow_site_order() {
+	let names: Vec<String> = bookmark_listings().into_iter().map(|l| l.name).collect();
+
+	assert_eq!(
+		names,
+		[
+			"Закладки: Читаю",
+			"Закладки: В планах",
+			"Закладки: Прочитано",
+			"Закладки: Брошено",
+			"Закладки: Любимые",
+		]
+	);
+}
+
+#[aidoku_test]
+fn folder_query_params() {
+	assert_eq!(
+		BookmarkFolder::Completed.query_params(),
+		[("bookmarks[]", "4".to_string())]
+	);
+}
@@ -1,7 +1,8 @@
 use aidoku::{
 	Result,
-	alloc::{String, collections::btree_map::BTreeMap},
+	alloc::{String, Vec, collections::btree_map::BTreeMap},
 	imports::{net::Request, std::current_date},
+	prelude::*,
 };
 use spin::{Once, RwLock};
 
@@ -10,6 +11,9 @@ use crate::{
 	settings::get_image_server_url,
 };
 
+/// Order in which image servers are tried after the selected one fails
+const SERVER_FALLBACK_ORDER: [&str; 3] = ["compress", "main", "secondary"];
+
 struct CacheEntry {
 	data: BTreeMap<u8, BTreeMap<String, String>>,
 	created_at: i64,
@@ -32,6 +36,7 @@ impl CacheEntry {
 /// - On miss or expired: we synchronously load.
 /// - If load fails and there is a stale entry, we return the stale entry.
 /// - If load fails and no entry exists, return empty string.
+/// - If the selected server isn't available for the site, fall back to the others.
 pub struct ImageServerCache {
 	cache: RwLock<Option<CacheEntry>>,
 	// TTL for the image server list: default 1 hour
@@ -57,7 +62,16 @@ impl ImageServerCache {
 
 	/// Public getter that returns the selected base URL (may be empty)
 	pub fn get_base_url(&self, ctx: &Context) -> String {
+		self.get_base_urls(ctx)
+			.into_iter()
+			.next()
+			.unwrap_or_default()
+	}
+
+	/// Base URLs of every image server for the site, selected server first
+	pub fn get_base_urls(&self, ctx: &Context) -> Vec<String> {
 		let now = (self.now_fn)();
+		let selected_id = get_image_server_url();
 
 		// Fast path: check cache under read lock
 		{
@@ -65,27 +79,24 @@ impl ImageServerCache {
 			if let Some(ref entry) = *guard
 				&& !entry.is_expired(now, self.ttl_seconds)
 			{
-				let selected_id = get_image_server_url();
-				return self.extract_url(&entry.data, &ctx.site_id, &selected_id);
+				return self.extract_urls(&entry.data, &ctx.site_id, &selected_id);
 			}
 		}
 
 		// Miss or expired: attempt reload synchronously.
 		match self.load_data(ctx) {
 			Ok(data) => {
-				let entry = CacheEntry::new(data.clone(), now);
-				*self.cache.write() = Some(entry);
-				let selected_id = get_image_server_url();
-				self.extract_url(&data, &ctx.site_id, &selected_id)
+				let urls = self.extract_urls(&data, &ctx.site_id, &selected_id);
+				*self.cache.write() = Some(CacheEntry::new(data, now));
+				urls
 			}
 			Err(_) => {
 				// Load failed: return stale if present, else empty
 				let guard = self.cache.read();
 				if let Some(ref entry) = *guard {
-					let selected_id = get_image_server_url();
-					self.extract_url(&entry.data, &ctx.site_id, &selected_id)
+					self.extract_urls(&entry.data, &ctx.site_id, &selected_id)
 				} else {
-					String::new()
+					Vec::new()
 				}
 			}
 		}
@@ -112,16 +123,31 @@ impl ImageServerCache {
 		Ok(servers_by_site)
 	}
 
-	fn extract_url(
+	/// Server URLs for the site: the selected one, then the fallback order, then any others
+	fn extract_urls(
 		&self,
 		data: &BTreeMap<u8, BTreeMap<String, String>>,
 		site_id: &u8,
 		server_id: &str,
-	) -> String {
-		data.get(site_id)
-			.and_then(|site_servers| site_servers.get(server_id))
-			.cloned()
-			.unwrap_or_default()
+	) -> Vec<String> {
+		let Some(site_servers) = data.get(site_id) else {
+			return Vec::new();
+		};
+
+		let mut ids: Vec<&str> = Vec::from([server_id]);
+		ids.extend(SERVER_FALLBACK_ORDER);
+		ids.extend(site_servers.keys().map(String::as_str));
+
+		let mut urls: Vec<String> = Vec::with_capacity(site_servers.len());
+		for id in ids {
+			if let Some(url) = site_servers.get(id)
+				&& !url.is_empty()
+				&& !urls.contains(url)
+			{
+				urls.push(url.clone());
+			}
+		}
+		urls
 	}
 }
 
@@ -135,5 +161,109 @@ pub fn get_selected_image_server_url(ctx: &Context) -> String {
 	get_image_server_cache().get_base_url(ctx)
 }
 
+/// How long an image server check is trusted before the server is checked again
+const SERVER_HEALTH_TTL: i64 = 10 * 60;
+
+/// Whether an image server answered, and when it was checked
+#[derive(Clone, Copy)]
+struct ServerHealth {
+	up: bool,
+	checked_at: i64,
+}
+
+/// Image server checks, shared between pages instead of repeated for every image
+static SERVER_HEALTH: RwLock<BTreeMap<String, ServerHealth>> = RwLock::new(BTreeMap::new());
+
+/// URL to request for a page image: the first candidate on a server that is up.
+///
+/// `is_up` is only called for servers that haven't been checked in the last `SERVER_HEALTH_TTL`.
+pub fn get_image_url(ctx: &Context, url: &str, is_up: impl Fn(&str) -> bool) -> String {
+	pick_image_url(
+		get_image_url_candidates(ctx, url),
+		&SERVER_HEALTH,
+		current_date(),
+		is_up,
+	)
+	.unwrap_or_else(|| url.into())
+}
+
+fn pick_image_url(
+	mut candidates: Vec<String>,
+	health: &RwLock<BTreeMap<String, ServerHealth>>,
+	now: i64,
+	is_up: impl Fn(&str) -> bool,
+) -> Option<String> {
+	// The last server is used as is, there's nothing left to fall back to
+	let last = candidates.pop()?;
+
+	for candidate in candidates {
+		let server = server_origin(&candidate);
+		let known = health
+			.read()
+			.get(server)
+			.filter(|check| now - check.checked_at <= SERVER_HEALTH_TTL)
+			.map(|check| check.up);
+		let up = match known {
+			Some(up) => up,
+			None => {
+				let up = is_up(&candidate);
+				health.write().insert(
+					server.into(),
+					ServerHealth {
+						up,
+						checked_at: now,
+					},
+				);
+				up
+			}
+		};
+		if up {
+			return Some(candidate);
+		}
+	}
+
+	Some(last)
+}
+
+/// `https://host` part of a URL
+fn server_origin(url: &str) -> &str {
+	let host_start = url.find("://").map(|i| i + 3).unwrap_or(0);
+	match url[host_start..].find('/') {
+		Some(i) => &url[..host_start + i],
+		None => url,
+	}
+}
+
+/// URLs to try for a page image: the original one, then the same path on the other servers
+fn get_image_url_candidates(ctx: &Context, url: &str) -> Vec<String> {
+	fallback_urls(&get_image_server_cache().get_base_urls(ctx), url)
+}
+
+fn fallback_urls(base_urls: &[String], url: &str) -> Vec<String> {
+	// Relative URLs come from pages built while no server was known
+	let path = if url.starts_with('/') {
+		Some(url)
+	} else {
+		base_urls
+			.iter()
+			.find_map(|base| url.strip_prefix(base.as_str()))
+			.filter(|path| path.starts_with('/'))
+	};
+
+	let mut urls = Vec::new();
+	if !url.starts_with('/') {
+		urls.push(url.into());
+	}
+	if let Some(path) = path {
+		for base in base_urls {
+			let candidate = format!("{base}{path}");
+			if !urls.contains(&candidate) {
+				urls.push(candidate);
+			}
+		}
+	}
+	urls
+}
+
 #[cfg(test)]
 mod test;
@@ -1,5 +1,5 @@
 use super::*;
-use aidoku::alloc::{String, string::ToString};
+use aidoku::alloc::{String, Vec, string::ToString};
 use aidoku_test::aidoku_test;
 
 fn test_context() -> Context {
@@ -34,9 +34,9 @@ fn cache_hit_returns_same_url() {
 		*guard = Some(CacheEntry::new(servers.clone(), fake_now()));
 	}
 
-	// extract_url directly from seeded map with explicit server_id
-	let url = cache.extract_url(&servers, &1u8, "server1");
-	assert_eq!(url, "http://img.server/1");
+	// extract_urls directly from seeded map with explicit server_id
+	let urls = cache.extract_urls(&servers, &1u8, "server1");
+	assert_eq!(urls, ["http://img.server/1"]);
 
 	// For get_base_url, you'd need to ensure get_image_server_url() returns "server1"
 	// or test it separately
@@ -64,9 +64,194 @@ fn expired_entry_detected() {
 }
 
 #[aidoku_test]
-fn extract_url_returns_empty_when_not_found() {
+fn extract_urls_returns_empty_when_not_found() {
 	let cache = make_cache_with_ttl(3600);
 	let servers: BTreeMap<u8, BTreeMap<String, String>> = BTreeMap::new();
-	let url = cache.extract_url(&servers, &99u8, "server1");
-	assert_eq!(url, "");
+	let urls = cache.extract_urls(&servers, &99u8, "server1");
+	assert!(urls.is_empty());
+}
+
+fn site_servers() -> BTreeMap<u8, BTreeMap<String, String>> {
+	let mut inner = BTreeMap::new();
+	inner.insert("main".to_string(), "https://img2.example".to_string());
+	inner.insert("secondary".to_string(), "https://img3.example".to_string());
+	inner.insert("compress".to_string(), "https://img4.example".to_string());
+
+	let mut servers = BTreeMap::new();
+	servers.insert(1u8, inner);
+	servers
+}
+
+#[aidoku_test]
+fn selected_server_comes_first() {
+	let cache = make_cache_with_ttl(3600);
+	let urls = cache.extract_urls(&site_servers(), &1u8, "secondary");
+
+	assert_eq!(
+		urls,
+		[
+			"https://img3.example",
+			"https://img4.example",
+			"https://img2.example"
+		]
+	);
+}
+
+#[aidoku_test]
+fn missing_server_falls_back_in_order() {
+	let cache = make_cache_with_ttl(3600);
+
+	let mut servers = site_servers();
+	servers.get_mut(&1u8).unwrap().remove("compress");
+
+	// The selected server isn't listed for the site: compress -> main -> secondary
+	let urls = cache.extract_urls(&servers, &1u8, "compress");
+	assert_eq!(urls, ["https://img2.example", "https://img3.example"]);
+
+	// An unknown id (e.g. from an old setting) also falls back
+	let urls = cache.extract_urls(&servers, &1u8, "removed");
+	assert_eq!(
+		urls.first().map(String::as_str),
+		Some("https://img2.example")
+	);
+}
+
+#[aidoku_test]
+fn stale_entry_used_when_reload_fails() {
+	let ctx = test_context();
+	let cache = make_cache_with_ttl(1);
+
+	{
+		let mut guard = cache.cache.write();
+		*guard = Some(CacheEntry::new(site_servers(), fake_now() - 10));
+	}
+
+	// The fake API can't be reached, so the expired entry is served instead of nothing
+	let urls = cache.get_base_urls(&ctx);
+	assert_eq!(urls.len(), 3);
+	assert!(!cache.get_base_url(&ctx).is_empty());
+}
+
+#[aidoku_test]
+fn fallback_urls_rewrite_server() {
+	let bases: Vec<String> = ["https://img4.example", "https://img2.example"]
+		.into_iter()
+		.map(String::from)
+		.collect();
+
+	assert_eq!(
+		fallback_urls(&bases, "https://img4.example/manga/a/1.jpg"),
+		[
+			"https://img4.example/manga/a/1.jpg",
+			"https://img2.example/manga/a/1.jpg"
+		]
+	);
+
+	// Relative URL from a missing server gets every server prepended
+	assert_eq!(
+		fallback_urls(&bases, "/manga/a/1.jpg"),
+		[
+			"https://img4.example/manga/a/1.jpg",
+			"https://img2.example/manga/a/1.jpg"
+		]
+	);
+
+	// Foreign URLs are left alone
+	assert_eq!(
+		fallback_urls(&bases, "https://other.example/1.jpg"),
+		["https://other.example/1.jpg"]
+	);
+}
+
+fn candidates() -> Vec<String> {
+	[
+		"https://img4.example/manga/a/1.jpg",
+		"https://img2.example/manga/a/1.jpg",
+		"https://img3.example/manga/a/1.jpg",
+	]
+	.into_iter()
+	.map(String::from)
+	.collect()
+}
+
+#[aidoku_test]
+fn image_server_checks_are_reused() {
+	let health = RwLock::new(BTreeMap::new());
+
+	let checked = RwLock::new(Vec::new());
+	let is_up = |url: &str| {
+		checked.write().push(server_origin(url).to_string());
+		!url.starts_with("https://img4.example")
+	};
+
+	assert_eq!(
+		pick_image_url(candidates(), &health, fake_now(), is_up).as_deref(),
+		Some("https://img2.example/manga/a/1.jpg")
+	);
+	// Later pages reuse the results instead of checking again
+	assert_eq!(
+		pick_image_url(candidates(), &health, fake_now() + 60, is_up).as_deref(),
+		Some("https://img2.example/manga/a/1.jpg")
+	);
+	assert_eq!(
+		*checked.read(),
+		["https://img4.example", "https://img2.example"]
+	);
+}
+
+#[aidoku_test]
+fn image_servers_checked_again_after_ttl() {
+	let health = RwLock::new(BTreeMap::new());
+	health.write().insert(
+		"https://img4.example".to_string(),
+		ServerHealth {
+			up: false,
+			checked_at: fake_now(),
+		},
+	);
+
+	// Still down within the TTL
+	assert_eq!(
+		pick_image_url(candidates(), &health, fake_now() + 60, |_| true).as_deref(),
+		Some("https://img2.example/manga/a/1.jpg")
+	);
+	// Back up once the check expired
+	assert_eq!(
+		pick_image_url(
+			candidates(),
+			&health,
+			fake_now() + SERVER_HEALTH_TTL + 1,
+			|_| true
+		)
+		.as_deref(),
+		Some("https://img4.example/manga/a/1.jpg")
+	);
+}
+
+#[aidoku_test]
+fn last_image_server_used_without_check() {
+	let health = RwLock::new(BTreeMap::new());
+	health.write().insert(
+		"https://img4.example".to_string(),
+		ServerHealth {
+			up: false,
+			checked_at: fake_now(),
+		},
+	);
+
+	let candidates = Vec::from([
+		"https://img4.example/1.jpg".to_string(),
+		"https://img2.example/1.jpg".to_string(),
+	]);
+	assert_eq!(
+		pick_image_url(candidates, &health, fake_now(), |_| panic!(
+			"no check needed"
+		))
+		.as_deref(),
+		Some("https://img2.example/1.jpg")
+	);
+	assert_eq!(
+		pick_image_url(Vec::new(), &health, fake_now(), |_| true),
+		None
+	);
 }
@@ -3,6 +3,7 @@ use aidoku::{
 	alloc::{String, Vec, collections::btree_map::BTreeMap, string::ToString},
 	imports::{net::Request, std::current_date},
 };
+use core::sync::atomic::{AtomicU64, Ordering};
 use spin::{Once, RwLock};
 
 use crate::{
@@ -12,10 +13,14 @@ use crate::{
 	models::{chapter::LibGroupChapterListItem, responses::ChaptersResponse},
 };
 
+/// Maximum number of titles kept in the cache
+const MAX_CACHED_MANGA: usize = 32;
+
 /// Timestamped entry
 struct TimedVec {
 	data: Vec<LibGroupChapterListItem>,
 	created_at: i64,
+	last_used: u64,
 }
 
 impl TimedVec {
@@ -23,6 +28,7 @@ impl TimedVec {
 		Self {
 			data,
 			created_at: now,
+			last_used: 0,
 		}
 	}
 
@@ -34,66 +40,94 @@ impl TimedVec {
 	}
 }
 
-/// Cache that maps manga_key -> chapters
+/// Cache that maps manga_key -> chapters, evicting the least recently used title when full
 pub struct ChaptersCache {
 	cache: RwLock<BTreeMap<String, TimedVec>>,
-	ttl_seconds: Option<i64>,
+	capacity: usize,
+	tick: AtomicU64,
 	now_fn: fn() -> i64,
 }
 
 impl ChaptersCache {
-	pub fn new_with_ttl(ttl_seconds: Option<i64>, now_fn: fn() -> i64) -> Self {
+	pub fn new(capacity: usize, now_fn: fn() -> i64) -> Self {
 		Self {
 			cache: RwLock::new(BTreeMap::new()),
-			ttl_seconds,
+			capacity: capacity.max(1),
+			tick: AtomicU64::new(0),
 			now_fn,
 		}
 	}
 
-	/// Get chapters: returns cached copy on hit, otherwise fetches, caches, and returns.
-	/// Single write-lock path: acquire write lock once to simplify logic & avoid double-locking.
+	/// Get chapters: returns cached copy if it is younger than `ttl_seconds`
+	/// (`None` accepts any age), otherwise fetches, caches, and returns.
 	pub fn get_chapters(
 		&self,
 		manga_key: &str,
 		ctx: &Context,
+		ttl_seconds: Option<i64>,
 	) -> Result<Vec<LibGroupChapterListItem>> {
 		let now = (self.now_fn)();
-		let mut guard = self.cache.write();
 
 		// Check cache hit and TTL
-		if let Some(entry) = guard.get(manga_key) {
-			if !entry.is_expired(now, self.ttl_seconds) {
+		{
+			let mut guard = self.cache.write();
+			if let Some(entry) = guard.get_mut(manga_key)
+				&& !entry.is_expired(now, ttl_seconds)
+			{
+				entry.last_used = self.next_tick();
 				return Ok(entry.data.clone());
 			}
-			// expired -> drop entry and fallthrough to reload
-			guard.remove(manga_key);
 		}
 
-		// Load remote
-		let chapters_url = Url::manga_chapters(&ctx.base_url, manga_key);
+		// Load remote, without holding the lock during the request
+		let chapters_url = Url::manga_chapters(&ctx.api_url, manga_key);
 		let chapters = Request::get(chapters_url)?
 			.authed(ctx)?
 			.get_json::<ChaptersResponse>()?
 			.data;
 
-		// Insert with timestamp
-		guard.insert(manga_key.to_string(), TimedVec::new(chapters.clone(), now));
+		self.insert(manga_key, chapters.clone());
 
 		Ok(chapters)
 	}
 
+	/// Store freshly fetched chapters, e.g. from a manga update.
+	pub fn insert(&self, manga_key: &str, chapters: Vec<LibGroupChapterListItem>) {
+		let now = (self.now_fn)();
+		let mut entry = TimedVec::new(chapters, now);
+		entry.last_used = self.next_tick();
+
+		let mut guard = self.cache.write();
+		guard.insert(manga_key.to_string(), entry);
+
+		while guard.len() > self.capacity {
+			let Some(oldest) = guard
+				.iter()
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(key, _)| key.clone())
+			else {
+				break;
+			};
+			guard.remove(&oldest);
+		}
+	}
+
 	/// Clear all cache entries.
 	pub fn clear(&self) {
 		let mut guard = self.cache.write();
 		guard.clear();
 	}
+
+	fn next_tick(&self) -> u64 {
+		self.tick.fetch_add(1, Ordering::Relaxed) + 1
+	}
 }
 
 static CHAPTERS_CACHE: Once<ChaptersCache> = Once::new();
 
 /// Global accessor — lazy init
-pub fn get_chapters_cache(ttl_seconds: Option<i64>) -> &'static ChaptersCache {
-	CHAPTERS_CACHE.call_once(|| ChaptersCache::new_with_ttl(ttl_seconds, current_date))
+pub fn get_chapters_cache() -> &'static ChaptersCache {
+	CHAPTERS_CACHE.call_once(|| ChaptersCache::new(MAX_CACHED_MANGA, current_date))
 }
 
 #[cfg(test)]
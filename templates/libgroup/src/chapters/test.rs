@@ -1,6 +1,10 @@
 use crate::chapters::{ChaptersCache, TimedVec};
 use crate::context::Context;
-use crate::models::chapter::LibGroupChapterListItem;
+use crate::models::{
+	chapter::{LibGroupChapterBranch, LibGroupChapterListItem, LockReason},
+	common::{LibGroupModerated, LibGroupRestrictedView, LibGroupTeam},
+};
+use crate::settings::BranchPreference;
 use aidoku::alloc::{string::ToString, vec};
 use aidoku_test::aidoku_test;
 
@@ -17,8 +21,8 @@ fn fake_now() -> i64 {
 	1_000_000
 }
 
-fn make_cache_with_ttl(ttl: Option<i64>) -> ChaptersCache {
-	ChaptersCache::new_with_ttl(ttl, fake_now)
+fn make_cache() -> ChaptersCache {
+	ChaptersCache::new(8, fake_now)
 }
 
 fn make_item(id: &str) -> LibGroupChapterListItem {
@@ -33,7 +37,7 @@ fn make_item(id: &str) -> LibGroupChapterListItem {
 #[aidoku_test]
 fn cache_hit_returns_same_data() {
 	let ctx = test_context();
-	let cache = make_cache_with_ttl(None);
+	let cache = make_cache();
 	let manga_key = "manga1";
 
 	let mut guard = cache.cache.write();
@@ -42,7 +46,7 @@ fn cache_hit_returns_same_data() {
 		TimedVec::new(vec![make_item("ch1")], fake_now()),
 	);
 
-	let chapters = cache.get_chapters(manga_key, &ctx);
+	let chapters = cache.get_chapters(manga_key, &ctx, None);
 	assert!(chapters.is_ok());
 	let chs = chapters.unwrap();
 	assert_eq!(chs.len(), 1);
@@ -52,7 +56,7 @@ fn cache_hit_returns_same_data() {
 #[aidoku_test]
 fn ttl_expiration_detected() {
 	static mut CURRENT_TIME: i64 = 1_000_000;
-	let cache = ChaptersCache::new_with_ttl(Some(10), || unsafe { CURRENT_TIME });
+	let cache = ChaptersCache::new(8, || unsafe { CURRENT_TIME });
 
 	let manga_key = "manga2";
 	unsafe { CURRENT_TIME = 1_000_000 };
@@ -76,7 +80,7 @@ fn ttl_expiration_detected() {
 
 #[aidoku_test]
 fn clear_removes_all_entries() {
-	let cache = make_cache_with_ttl(None);
+	let cache = make_cache();
 	let manga_key = "manga3";
 
 	{
@@ -90,3 +94,176 @@ fn clear_removes_all_entries() {
 	cache.clear();
 	assert!(cache.cache.read().is_empty());
 }
+
+#[aidoku_test]
+fn ttl_is_per_call() {
+	static mut CURRENT_TIME: i64 = 1_000_000;
+	let ctx = test_context();
+	let cache = ChaptersCache::new(8, || unsafe { CURRENT_TIME });
+
+	cache.insert("manga4", vec![make_item("ch1")]);
+	unsafe { CURRENT_TIME += 20 };
+
+	// No TTL accepts any age, a short TTL forces a reload (which fails against the fake API)
+	assert!(cache.get_chapters("manga4", &ctx, None).is_ok());
+	assert!(cache.get_chapters("manga4", &ctx, Some(3600)).is_ok());
+	assert!(cache.get_chapters("manga4", &ctx, Some(10)).is_err());
+}
+
+#[aidoku_test]
+fn insert_populates_cache() {
+	let ctx = test_context();
+	let cache = make_cache();
+
+	cache.insert("manga5", vec![make_item("ch1"), make_item("ch2")]);
+
+	let chapters = cache.get_chapters("manga5", &ctx, Some(3600)).unwrap();
+	assert_eq!(chapters.len(), 2);
+}
+
+#[aidoku_test]
+fn least_recently_used_entry_is_evicted() {
+	let ctx = test_context();
+	let cache = ChaptersCache::new(2, fake_now);
+
+	cache.insert("a", vec![make_item("1")]);
+	cache.insert("b", vec![make_item("1")]);
+
+	// Touch "a" so that "b" becomes the least recently used
+	assert!(cache.get_chapters("a", &ctx, None).is_ok());
+	cache.insert("c", vec![make_item("1")]);
+
+	let guard = cache.cache.read();
+	assert_eq!(guard.len(), 2);
+	assert!(guard.contains_key("a"));
+	assert!(!guard.contains_key("b"));
+	assert!(guard.contains_key("c"));
+}
+
+fn make_branch(
+	id: i32,
+	team: (i32, &str),
+	created_at: &str,
+	is_open: bool,
+) -> LibGroupChapterBranch {
+	LibGroupChapterBranch {
+		id,
+		created_at: created_at.to_string(),
+		teams: vec![LibGroupTeam {
+			id: team.0,
+			name: team.1.to_string(),
+		}],
+		restricted_view: Some(LibGroupRestrictedView {
+			is_open,
+			..Default::default()
+		}),
+		..Default::default()
+	}
+}
+
+fn make_multi_branch_item() -> LibGroupChapterListItem {
+	LibGroupChapterListItem {
+		volume: "1".to_string(),
+		number: "1".to_string(),
+		name: None,
+		branches: vec![
+			make_branch(10, (1, "Alpha"), "2024-01-01T00:00:00.000000Z", true),
+			make_branch(20, (2, "Бета"), "2024-03-01T00:00:00.000000Z", true),
+			make_branch(30, (3, "Gamma"), "2024-05-01T00:00:00.000000Z", false),
+		],
+	}
+}
+
+#[aidoku_test]
+fn all_branches_keep_every_chapter() {
+	let item = make_multi_branch_item();
+	// Migration keeps using the first branch
+	assert_eq!(
+		item.preferred_branch(&BranchPreference::All).map(|b| b.id),
+		Some(10)
+	);
+
+	let chapters = item.into_chapters("http://fake.base", "manga", &None, &BranchPreference::All);
+	assert_eq!(chapters.len(), 3);
+}
+
+#[aidoku_test]
+fn newest_branch_prefers_readable() {
+	let item = make_multi_branch_item();
+	let branch = item.preferred_branch(&BranchPreference::Newest);
+	// Gamma is newer but locked
+	assert_eq!(branch.map(|b| b.id), Some(20));
+
+	let chapters = item.into_chapters(
+		"http://fake.base",
+		"manga",
+		&None,
+		&BranchPreference::Newest,
+	);
+	assert_eq!(chapters.len(), 1);
+	assert_eq!(chapters[0].key, "20");
+}
+
+#[aidoku_test]
+fn pinned_branch_matches_name_or_id() {
+	let item = make_multi_branch_item();
+
+	let by_name = item.preferred_branch(&BranchPreference::Pinned("бета".to_string()));
+	assert_eq!(by_name.map(|b| b.id), Some(20));
+
+	let by_id = item.preferred_branch(&BranchPreference::Pinned("3".to_string()));
+	assert_eq!(by_id.map(|b| b.id), Some(30));
+
+	// Unknown team falls back to the newest readable branch
+	let fallback = item.preferred_branch(&BranchPreference::Pinned("Delta".to_string()));
+	assert_eq!(fallback.map(|b| b.id), Some(20));
+}
+
+#[aidoku_test]
+fn purchased_chapters_are_not_locked() {
+	let mut branch = make_branch(40, (4, "Delta"), "2024-06-01T00:00:00.000000Z", false);
+	assert_eq!(branch.lock_reason(), Some(LockReason::Paid(None)));
+
+	branch.restricted_view = Some(LibGroupRestrictedView {
+		is_open: false,
+		is_purchased: true,
+		..Default::default()
+	});
+	assert_eq!(branch.lock_reason(), None);
+
+	branch.restricted_view = Some(LibGroupRestrictedView {
+		is_open: false,
+		is_subscribed: true,
+		..Default::default()
+	});
+	let chapters = LibGroupChapterListItem {
+		branches: vec![branch],
+		..Default::default()
+	}
+	.into_chapters("http://fake.base", "manga", &None, &BranchPreference::All);
+	assert!(!chapters[0].locked);
+}
+
+#[aidoku_test]
+fn early_access_reports_free_date() {
+	let mut branch = make_branch(50, (5, "Epsilon"), "2024-06-01T00:00:00.000000Z", true);
+	branch.restricted_view = Some(LibGroupRestrictedView {
+		is_open: false,
+		end_date: Some("2024-06-08T12:00:00.000000Z".to_string()),
+		..Default::default()
+	});
+
+	let reason = branch.lock_reason();
+	assert_eq!(reason, Some(LockReason::Paid(Some(1717848000))));
+	assert!(reason.unwrap().message().contains("08.06.2024"));
+}
+
+#[aidoku_test]
+fn moderated_chapters_are_locked() {
+	let mut branch = make_branch(60, (6, "Zeta"), "2024-06-01T00:00:00.000000Z", true);
+	branch.moderation = Some(LibGroupModerated {
+		label: "На модерации".to_string(),
+	});
+
+	assert_eq!(branch.lock_reason(), Some(LockReason::Moderation));
+}
@@ -0,0 +1,189 @@
+use aidoku::{
+	AidokuError, Page, PageContent, PageContext, Result,
+	alloc::{String, Vec, string::ToString},
+	imports::net::Request,
+	prelude::*,
+};
+use spin::RwLock;
+
+use crate::{
+	auth::AuthRequest,
+	cdn::get_selected_image_server_url,
+	context::Context,
+	endpoints::Url,
+	models::{comment::LibGroupComment, responses::CommentsResponse},
+};
+
+/// Page context key holding the chapter id of the described page
+const CHAPTER_ID_KEY: &str = "chapter_id";
+/// Number of recently opened chapters whose notes are kept
+const MAX_REMEMBERED_CHAPTERS: usize = 4;
+/// Number of comments shown under a chapter
+const MAX_COMMENTS: usize = 5;
+
+struct ChapterThread {
+	chapter_id: String,
+	note: Option<String>,
+	/// Fingerprint of the described page when it's a text page
+	text_page: Option<u64>,
+}
+
+/// Notes of recently opened chapters, newest last.
+///
+/// Text pages can't carry a context, so they are matched by a fingerprint of their text.
+struct ChapterThreads {
+	threads: RwLock<Vec<ChapterThread>>,
+}
+
+impl ChapterThreads {
+	const fn new() -> Self {
+		Self {
+			threads: RwLock::new(Vec::new()),
+		}
+	}
+
+	fn remember(&self, chapter_id: &str, note: Option<String>, text_page: Option<u64>) {
+		let mut threads = self.threads.write();
+		threads.retain(|thread| thread.chapter_id != chapter_id);
+		if threads.len() >= MAX_REMEMBERED_CHAPTERS {
+			threads.remove(0);
+		}
+		threads.push(ChapterThread {
+			chapter_id: chapter_id.into(),
+			note,
+			text_page,
+		});
+	}
+
+	/// Chapter id and note for a described page
+	fn find(&self, page: &Page) -> Option<(String, Option<String>)> {
+		let threads = self.threads.read();
+		let (thread, context_id) = match &page.content {
+			PageContent::Url(_, Some(context)) => {
+				let id = context.get(CHAPTER_ID_KEY).cloned();
+				let thread = id
+					.as_ref()
+					.and_then(|id| threads.iter().find(|thread| &thread.chapter_id == id));
+				(thread, id)
+			}
+			PageContent::Text(text) => {
+				let fingerprint = Some(fingerprint(text));
+				let thread = threads
+					.iter()
+					.rev()
+					.find(|thread| thread.text_page == fingerprint);
+				(thread, None)
+			}
+			_ => (None, None),
+		};
+
+		match (thread, context_id) {
+			(Some(thread), _) => Some((thread.chapter_id.clone(), thread.note.clone())),
+			// The note is lost, but comments can still be loaded
+			(None, Some(id)) => Some((id, None)),
+			(None, None) => None,
+		}
+	}
+}
+
+static THREADS: ChapterThreads = ChapterThreads::new();
+
+/// Mark the last page of a chapter as described and remember the team note for it
+pub fn attach_description(pages: &mut [Page], chapter_id: &str, note: Option<String>) {
+	mark_last_page(pages, chapter_id, note, &THREADS);
+}
+
+fn mark_last_page(
+	pages: &mut [Page],
+	chapter_id: &str,
+	note: Option<String>,
+	threads: &ChapterThreads,
+) {
+	let Some(last) = pages.last_mut() else {
+		return;
+	};
+
+	last.has_description = true;
+	let text_page = match &mut last.content {
+		PageContent::Url(_, context) => {
+			context
+				.get_or_insert_with(PageContext::new)
+				.insert(CHAPTER_ID_KEY.into(), chapter_id.into());
+			None
+		}
+		PageContent::Text(text) => Some(fingerprint(text)),
+		_ => None,
+	};
+
+	threads.remember(chapter_id, note, text_page);
+}
+
+/// FNV-1a hash of a text page, so the whole text doesn't have to be kept
+fn fingerprint(text: &str) -> u64 {
+	text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+		(hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+	})
+}
+
+/// Build the description of a page marked by [`attach_description`]
+pub fn get_page_description(ctx: &Context, page: &Page) -> Result<String> {
+	let (chapter_id, note) = THREADS
+		.find(page)
+		.ok_or_else(|| AidokuError::message("Chapter not found"))?;
+
+	let comments = Request::get(Url::chapter_comments(&ctx.api_url, &chapter_id))?
+		.authed(ctx)?
+		.get_json::<CommentsResponse>()
+		.map(|response| response.data)
+		.unwrap_or_default();
+
+	Ok(format_description(
+		note.as_deref(),
+		&comments,
+		&get_selected_image_server_url(ctx),
+	))
+}
+
+fn format_description(
+	note: Option<&str>,
+	comments: &[LibGroupComment],
+	image_base: &str,
+) -> String {
+	let mut sections = Vec::new();
+
+	if let Some(note) = note.map(str::trim).filter(|note| !note.is_empty()) {
+		sections.push(format!("**Примечание команды**\n\n{note}"));
+	}
+
+	let comments: Vec<String> = comments
+		.iter()
+		.filter_map(|comment| {
+			let text = comment.comment.to_markdown(image_base);
+			let text = text.trim();
+			if text.is_empty() {
+				return None;
+			}
+			let score = comment.votes.up - comment.votes.down;
+			let score = if score > 0 {
+				format!("+{score}")
+			} else {
+				score.to_string()
+			};
+			Some(format!("**{}** ({score})\n\n{text}", comment.user.username))
+		})
+		.take(MAX_COMMENTS)
+		.collect();
+
+	if !comments.is_empty() {
+		sections.push(format!("**Комментарии**\n\n{}", comments.join("\n\n")));
+	}
+
+	if sections.is_empty() {
+		String::from("Комментариев пока нет")
+	} else {
+		sections.join("\n\n---\n\n")
+	}
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,141 @@
+use super::*;
+use aidoku::alloc::vec;
+use aidoku_test::aidoku_test;
+
+fn comment(json: &str) -> LibGroupComment {
+	serde_json::from_str(json).expect("valid comment json")
+}
+
+fn image_page(url: &str) -> Page {
+	Page {
+		content: PageContent::url(String::from(url)),
+		..Default::default()
+	}
+}
+
+#[aidoku_test]
+fn description_with_note_and_comments() {
+	let comments = [
+		comment(
+			r#"{"id":1,"user":{"username":"reader"},"votes":{"up":12,"down":2},
+			"comment":{"type":"doc","content":[{"type":"paragraph","content":[{"type":"text","text":"Спасибо за перевод!"}]}]}}"#,
+		),
+		comment(
+			r#"{"id":2,"user":{"username":"critic"},"votes":{"up":0,"down":3},
+			"comment":{"type":"doc","content":[{"type":"paragraph","content":[{"type":"text","marks":[{"type":"bold"}],"text":"Спойлер"}]}]}}"#,
+		),
+	];
+
+	assert_eq!(
+		format_description(Some(" Термины сверены с анлейтом. "), &comments, ""),
+		"**Примечание команды**\n\nТермины сверены с анлейтом.\n\n---\n\n\
+		**Комментарии**\n\n\
+		**reader** (+10)\n\nСпасибо за перевод!\n\n\
+		**critic** (-3)\n\n**Спойлер**"
+	);
+}
+
+#[aidoku_test]
+fn description_skips_empty_comments_and_limits_count() {
+	let empty = comment(r#"{"id":1,"user":{"username":"ghost"},"comment":{"type":"doc"}}"#);
+	let text = comment(
+		r#"{"id":2,"user":{"username":"fan"},
+		"comment":{"type":"doc","content":[{"type":"paragraph","content":[{"type":"text","text":"ок"}]}]}}"#,
+	);
+	let mut comments = vec![empty];
+	comments.extend((0..10).map(|_| text.clone()));
+
+	let description = format_description(None, &comments, "");
+
+	assert!(!description.contains("ghost"));
+	assert_eq!(description.matches("**fan** (0)").count(), MAX_COMMENTS);
+}
+
+#[aidoku_test]
+fn description_without_content() {
+	assert_eq!(
+		format_description(Some("  "), &[], ""),
+		"Комментариев пока нет"
+	);
+}
+
+#[aidoku_test]
+fn only_last_page_is_described() {
+	let mut pages = vec![
+		image_page("https://img/1.jpg"),
+		image_page("https://img/2.jpg"),
+	];
+	let threads = ChapterThreads::new();
+	mark_last_page(&mut pages, "1", Some("Примечание".into()), &threads);
+
+	assert!(!pages[0].has_description);
+	assert!(pages[1].has_description);
+	assert_eq!(
+		threads.find(&pages[1]),
+		Some(("1".into(), Some("Примечание".into())))
+	);
+}
+
+#[aidoku_test]
+fn context_selects_chapter_over_newest() {
+	let mut first = vec![image_page("https://img/a.jpg")];
+	let mut second = vec![image_page("https://img/b.jpg")];
+	let threads = ChapterThreads::new();
+	mark_last_page(&mut first, "1", Some("первая".into()), &threads);
+	mark_last_page(&mut second, "2", Some("вторая".into()), &threads);
+
+	assert_eq!(
+		threads.find(&first[0]),
+		Some(("1".into(), Some("первая".into())))
+	);
+}
+
+#[aidoku_test]
+fn text_pages_are_matched_by_text() {
+	let text_page = |text: &str| Page {
+		content: PageContent::text(text),
+		..Default::default()
+	};
+	let mut first = vec![text_page("Текст первой главы")];
+	let mut second = vec![text_page("Текст второй главы")];
+	let threads = ChapterThreads::new();
+	mark_last_page(&mut first, "1", Some("первая".into()), &threads);
+	// The next chapter is preloaded while the first one is still read
+	mark_last_page(&mut second, "2", None, &threads);
+
+	assert!(first[0].has_description);
+	assert_eq!(
+		threads.find(&first[0]),
+		Some(("1".into(), Some("первая".into())))
+	);
+	assert_eq!(threads.find(&second[0]), Some(("2".into(), None)));
+	// A page that was never described isn't guessed
+	assert_eq!(threads.find(&text_page("Другой текст")), None);
+}
+
+#[aidoku_test]
+fn empty_chapter_is_ignored() {
+	let mut pages: Vec<Page> = Vec::new();
+	let threads = ChapterThreads::new();
+	mark_last_page(&mut pages, "1", None, &threads);
+
+	assert!(threads.threads.read().is_empty());
+}
+
+#[aidoku_test]
+fn only_recent_chapters_are_remembered() {
+	let threads = ChapterThreads::new();
+	for id in 0..=MAX_REMEMBERED_CHAPTERS {
+		threads.remember(&id.to_string(), None, None);
+	}
+	let mut context = PageContext::new();
+	context.insert(CHAPTER_ID_KEY.into(), "0".into());
+	let page = Page {
+		content: PageContent::url_context(String::from("https://img/0.jpg"), context),
+		..Default::default()
+	};
+
+	assert_eq!(threads.threads.read().len(), MAX_REMEMBERED_CHAPTERS);
+	// Comments of a forgotten chapter are still available, only the note is gone
+	assert_eq!(threads.find(&page), Some(("0".into(), None)));
+}
@@ -1,5 +1,70 @@
+use aidoku::alloc::{String, Vec};
+
 mod html_to_markdown;
 mod model_to_markdown;
 
 pub use html_to_markdown::convert_html_to_markdown;
-pub use model_to_markdown::convert_model_to_markdown;
+pub use model_to_markdown::convert_model_to_parts;
+
+/// Longest text page in bytes before a chapter is split into several pages
+pub const MAX_TEXT_PAGE_LEN: usize = 16_000;
+
+/// Piece of a text chapter: markdown text or an image shown as its own page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentPart {
+	Text(String),
+	Image(String),
+}
+
+/// Split markdown into pages of at most `max_len` bytes at paragraph boundaries.
+///
+/// A single paragraph longer than `max_len` is kept whole on its own page,
+/// and code blocks with blank lines in them count as one paragraph.
+pub fn split_text_pages(markdown: &str, max_len: usize) -> Vec<String> {
+	let mut pages = Vec::new();
+	let mut current = String::new();
+
+	for paragraph in paragraphs(markdown).iter().filter(|p| !p.trim().is_empty()) {
+		if !current.is_empty() && current.len() + 2 + paragraph.len() > max_len {
+			pages.push(core::mem::take(&mut current));
+		}
+		if !current.is_empty() {
+			current.push_str("\n\n");
+		}
+		current.push_str(paragraph);
+	}
+
+	if !current.is_empty() {
+		pages.push(current);
+	}
+
+	pages
+}
+
+/// Blocks separated by blank lines, without cutting fenced code blocks
+fn paragraphs(markdown: &str) -> Vec<String> {
+	let mut paragraphs: Vec<String> = Vec::new();
+	let mut in_code = false;
+
+	for block in markdown.split("\n\n") {
+		match paragraphs.last_mut() {
+			Some(last) if in_code => {
+				last.push_str("\n\n");
+				last.push_str(block);
+			}
+			_ => paragraphs.push(block.into()),
+		}
+		let fences = block
+			.lines()
+			.filter(|line| line.trim_start().starts_with("```"))
+			.count();
+		if fences % 2 == 1 {
+			in_code = !in_code;
+		}
+	}
+
+	paragraphs
+}
+
+#[cfg(test)]
+mod test;
@@ -1,175 +1,357 @@
 use aidoku::{
-	alloc::{String, string::ToString},
+	alloc::{String, Vec, string::ToString},
 	prelude::*,
 };
 
-use crate::{
-	cdn::get_selected_image_server_url,
-	context::Context,
-	models::chapter::{
-		LibGroupAttachment, LibGroupContentModel, LibGroupContentNode, LibGroupTextNode,
-	},
+use super::ContentPart;
+use crate::models::chapter::{
+	LibGroupAttachment, LibGroupContentModel, LibGroupContentNode, LibGroupImageAttr,
 };
 
-pub fn convert_model_to_markdown(
+/// Convert a ProseMirror-style chapter into markdown text and standalone images.
+///
+/// Relative image URLs are resolved against `image_base`, the selected image server.
+pub fn convert_model_to_parts(
 	model: &LibGroupContentModel,
 	attachments: &[LibGroupAttachment],
-	ctx: &Context,
-) -> String {
-	let mut markdown = String::new();
+	image_base: &str,
+) -> Vec<ContentPart> {
+	let mut renderer = Renderer {
+		attachments,
+		image_base,
+		parts: Vec::new(),
+		current: String::new(),
+		footnotes: Vec::new(),
+		nested: 0,
+	};
 
 	if let Some(content) = &model.content {
-		for node in content {
-			convert_node_to_markdown(node, &mut markdown, attachments, ctx);
-		}
+		renderer.blocks(content);
 	}
 
-	markdown.trim().to_string()
+	renderer.finish()
 }
 
-fn convert_node_to_markdown(
-	node: &LibGroupContentNode,
-	output: &mut String,
-	attachments: &[LibGroupAttachment],
-	ctx: &Context,
-) {
-	match node.node_type.as_str() {
-		"paragraph" => {
-			if let Some(content) = &node.content {
-				for text_node in content {
-					convert_text_node_to_markdown(text_node, output);
-				}
+struct Renderer<'a> {
+	attachments: &'a [LibGroupAttachment],
+	image_base: &'a str,
+	parts: Vec<ContentPart>,
+	/// Markdown of the text part being built
+	current: String,
+	footnotes: Vec<String>,
+	/// Depth of blockquotes, lists and tables; images there stay inline
+	nested: usize,
+}
+
+impl Renderer<'_> {
+	fn finish(mut self) -> Vec<ContentPart> {
+		if !self.footnotes.is_empty() {
+			self.current.push_str("---\n\n");
+			for (i, note) in self.footnotes.iter().enumerate() {
+				self.current.push_str(&format!("{}. {note}\n", i + 1));
 			}
-			output.push_str("\n\n");
 		}
-		"image" | "images" => {
-			if let Some(attrs) = &node.attrs
-				&& let Some(images) = &attrs.images
-			{
-				for img in images {
-					let image_url = attachments
-						.iter()
-						.find(|a| {
-							a.name.as_ref() == Some(&img.image)
-								|| a.filename
-									.as_ref()
-									.and_then(|f| f.split('.').next())
-									.map(|name| name == img.image)
-									.unwrap_or(false)
-						})
-						.map(|attachment| {
-							let url = &attachment.url;
-							if url.starts_with("http://") || url.starts_with("https://") {
-								url.clone()
-							} else if url.starts_with('/') {
-								format!("{}{}", ctx.base_url.trim_end_matches('/'), url)
-							} else {
-								format!("{}/{}", ctx.base_url.trim_end_matches('/'), url)
-							}
-						})
-						.unwrap_or_else(|| {
-							if img.image.starts_with("http://") || img.image.starts_with("https://")
-							{
-								img.image.clone()
-							} else {
-								format!("{}{}", get_selected_image_server_url(ctx), img.image)
-							}
-						});
-
-					output.push_str(&format!("![]({image_url})\n\n"));
-				}
-			}
+		self.flush_text();
+		self.parts
+	}
+
+	fn flush_text(&mut self) {
+		let text = self.current.trim();
+		if !text.is_empty() {
+			self.parts.push(ContentPart::Text(text.to_string()));
 		}
-		"heading" => {
-			output.push_str("## ");
-			if let Some(content) = &node.content {
-				for text_node in content {
-					convert_text_node_to_markdown(text_node, output);
-				}
-			}
-			output.push_str("\n\n");
+		self.current.clear();
+	}
+
+	fn push_block(&mut self, markdown: &str) {
+		let markdown = markdown.trim_end();
+		if !markdown.trim().is_empty() {
+			self.current.push_str(markdown);
+			self.current.push_str("\n\n");
 		}
-		"horizontalRule" | "hr" => {
-			output.push_str("---\n\n");
+	}
+
+	fn blocks(&mut self, nodes: &[LibGroupContentNode]) {
+		for node in nodes {
+			self.block(node);
 		}
-		"blockquote" => {
-			output.push_str("> ");
-			if let Some(content) = &node.content {
-				for text_node in content {
-					convert_text_node_to_markdown(text_node, output);
+	}
+
+	/// Render nested blocks into a separate string
+	fn capture_blocks(&mut self, nodes: &[LibGroupContentNode]) -> String {
+		let outer = core::mem::take(&mut self.current);
+		self.nested += 1;
+		self.blocks(nodes);
+		self.nested -= 1;
+		let inner = core::mem::replace(&mut self.current, outer);
+		inner.trim_end().to_string()
+	}
+
+	fn block(&mut self, node: &LibGroupContentNode) {
+		let children = node.content.as_deref().unwrap_or_default();
+
+		match node.node_type.as_str() {
+			"doc" => self.blocks(children),
+			"paragraph" => {
+				let text = self.inline(children);
+				self.push_block(&text);
+			}
+			"heading" => {
+				let level = node
+					.attrs
+					.as_ref()
+					.and_then(|attrs| attrs.level)
+					.unwrap_or(2)
+					.clamp(1, 6);
+				let text = self.inline(children);
+				if !text.trim().is_empty() {
+					let hashes = "#".repeat(level as usize);
+					self.push_block(&format!("{hashes} {}", text.trim()));
 				}
 			}
-			output.push_str("\n\n");
-		}
-		"codeBlock" | "code_block" => {
-			output.push_str("```\n");
-			if let Some(content) = &node.content {
-				for text_node in content {
-					if let Some(text) = &text_node.text {
-						output.push_str(text);
+			"horizontalRule" | "hr" => self.push_block("---"),
+			"blockquote" => {
+				let inner = self.capture_blocks(children);
+				let quoted = inner
+					.lines()
+					.map(|line| {
+						if line.is_empty() {
+							">".to_string()
+						} else {
+							format!("> {line}")
+						}
+					})
+					.collect::<Vec<_>>()
+					.join("\n");
+				self.push_block(&quoted);
+			}
+			"codeBlock" | "code_block" => {
+				let code = plain_text(children);
+				self.push_block(&format!("```\n{code}\n```"));
+			}
+			"bulletList" | "bullet_list" | "orderedList" | "ordered_list" => {
+				let ordered = node.node_type.starts_with("ordered");
+				let mut list = String::new();
+				for (i, item) in children.iter().enumerate() {
+					let marker = if ordered {
+						format!("{}. ", i + 1)
+					} else {
+						String::from("- ")
+					};
+					let text = match item.content.as_deref() {
+						// listItem wraps paragraphs, but older chapters put text nodes directly
+						Some(item_children) if item.node_type == "listItem" => {
+							self.capture_blocks(item_children)
+						}
+						_ => self.inline(core::slice::from_ref(item)),
+					};
+					let indent = " ".repeat(marker.chars().count());
+					for (j, line) in text.lines().filter(|l| !l.is_empty()).enumerate() {
+						if j == 0 {
+							list.push_str(&marker);
+						} else {
+							list.push_str(&indent);
+						}
+						list.push_str(line);
+						list.push('\n');
 					}
 				}
+				self.push_block(&list);
 			}
-			output.push_str("\n```\n\n");
-		}
-		"bulletList" | "bullet_list" => {
-			if let Some(content) = &node.content {
-				for item in content.iter() {
-					output.push_str("- ");
-					convert_text_node_to_markdown(item, output);
-					output.push('\n');
+			"table" => {
+				let table = self.table(children);
+				self.push_block(&table);
+			}
+			"image" | "images" => {
+				for image in node
+					.attrs
+					.as_ref()
+					.and_then(|attrs| attrs.images.as_deref())
+					.unwrap_or_default()
+				{
+					let url = self.image_url(image);
+					if self.nested > 0 {
+						self.push_block(&format!("![]({url})"));
+					} else {
+						self.flush_text();
+						self.parts.push(ContentPart::Image(url));
+					}
+				}
+			}
+			_ => {
+				// Unknown wrapper: treat inline content as a paragraph, otherwise keep walking
+				if children.iter().any(is_inline) {
+					let text = self.inline(children);
+					self.push_block(&text);
+				} else {
+					self.blocks(children);
 				}
 			}
-			output.push('\n');
 		}
-		"orderedList" | "ordered_list" => {
-			if let Some(content) = &node.content {
-				for (i, item) in content.iter().enumerate() {
-					output.push_str(&format!("{}. ", i + 1));
-					convert_text_node_to_markdown(item, output);
-					output.push('\n');
+	}
+
+	fn inline(&mut self, nodes: &[LibGroupContentNode]) -> String {
+		let mut output = String::new();
+		for node in nodes {
+			match node.node_type.as_str() {
+				"hardBreak" | "hard_break" => output.push_str("  \n"),
+				"footnote" | "footnoteReference" => {
+					let note = node
+						.attrs
+						.as_ref()
+						.and_then(|attrs| attrs.text.clone())
+						.unwrap_or_else(|| plain_text(node.content.as_deref().unwrap_or_default()));
+					if !note.trim().is_empty() {
+						self.footnotes.push(note.trim().to_string());
+						output.push_str(&format!("[{}]", self.footnotes.len()));
+					}
+				}
+				"image" | "images" => {
+					for image in node
+						.attrs
+						.as_ref()
+						.and_then(|attrs| attrs.images.as_deref())
+						.unwrap_or_default()
+					{
+						output.push_str(&format!("![]({})", self.image_url(image)));
+					}
+				}
+				_ => {
+					if let Some(text) = &node.text {
+						output.push_str(&apply_marks(node, text));
+					} else if let Some(children) = &node.content {
+						let text = self.inline(children);
+						output.push_str(&text);
+					}
 				}
 			}
-			output.push('\n');
 		}
-		_ => {
-			if let Some(content) = &node.content {
-				for text_node in content {
-					convert_text_node_to_markdown(text_node, output);
+		output
+	}
+
+	fn table(&mut self, rows: &[LibGroupContentNode]) -> String {
+		let cells: Vec<Vec<String>> = rows
+			.iter()
+			.map(|row| {
+				row.content
+					.as_deref()
+					.unwrap_or_default()
+					.iter()
+					.map(|cell| {
+						let text = self.capture_blocks(cell.content.as_deref().unwrap_or_default());
+						text.split('\n')
+							.map(str::trim)
+							.filter(|line| !line.is_empty())
+							.collect::<Vec<_>>()
+							.join(" ")
+							.replace('|', "\\|")
+					})
+					.collect()
+			})
+			.collect();
+
+		let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+		if columns == 0 {
+			return String::new();
+		}
+
+		let mut table = String::new();
+		for (i, row) in cells.iter().enumerate() {
+			table.push('|');
+			for column in 0..columns {
+				let cell = row.get(column).map(String::as_str).unwrap_or_default();
+				table.push_str(&format!(" {cell} |"));
+			}
+			table.push('\n');
+
+			// Markdown needs a header row, the first row serves as one
+			if i == 0 {
+				table.push('|');
+				for _ in 0..columns {
+					table.push_str(" --- |");
 				}
+				table.push('\n');
 			}
 		}
+		table
+	}
+
+	/// Find the attachment an image refers to and build its absolute URL
+	fn image_url(&self, image: &LibGroupImageAttr) -> String {
+		let url = self
+			.attachments
+			.iter()
+			.find(|a| {
+				a.name.as_ref() == Some(&image.image)
+					|| a.filename
+						.as_ref()
+						.and_then(|f| f.split('.').next())
+						.map(|name| name == image.image)
+						.unwrap_or(false)
+			})
+			.map(|attachment| attachment.url.as_str())
+			.unwrap_or(image.image.as_str());
+
+		if url.starts_with("http://") || url.starts_with("https://") {
+			url.to_string()
+		} else if url.starts_with('/') {
+			format!("{}{url}", self.image_base.trim_end_matches('/'))
+		} else {
+			format!("{}/{url}", self.image_base.trim_end_matches('/'))
+		}
+	}
+}
+
+fn is_inline(node: &LibGroupContentNode) -> bool {
+	node.text.is_some()
+		|| matches!(
+			node.node_type.as_str(),
+			"text" | "hardBreak" | "hard_break" | "footnote" | "footnoteReference"
+		)
+}
+
+fn plain_text(nodes: &[LibGroupContentNode]) -> String {
+	let mut output = String::new();
+	for node in nodes {
+		if let Some(text) = &node.text {
+			output.push_str(text);
+		} else if let Some(children) = &node.content {
+			output.push_str(&plain_text(children));
+		}
 	}
+	output
 }
 
-fn convert_text_node_to_markdown(text_node: &LibGroupTextNode, output: &mut String) {
-	if let Some(text) = &text_node.text {
-		let mut formatted_text = text.clone();
+fn apply_marks(node: &LibGroupContentNode, text: &str) -> String {
+	let mut formatted_text = text.to_string();
 
-		if let Some(marks) = &text_node.marks {
-			for mark in marks.iter().rev() {
-				match mark.mark_type.as_str() {
-					"bold" | "strong" => {
-						formatted_text = format!("**{formatted_text}**");
-					}
-					"italic" | "em" => {
-						formatted_text = format!("*{formatted_text}*");
-					}
-					"underline" => {
-						formatted_text = format!("__{formatted_text}__");
-					}
-					"strike" | "strikethrough" => {
-						formatted_text = format!("~~{formatted_text}~~");
-					}
-					"code" => {
-						formatted_text = format!("`{formatted_text}`");
+	if let Some(marks) = &node.marks {
+		for mark in marks.iter().rev() {
+			match mark.mark_type.as_str() {
+				"bold" | "strong" => {
+					formatted_text = format!("**{formatted_text}**");
+				}
+				"italic" | "em" => {
+					formatted_text = format!("*{formatted_text}*");
+				}
+				"underline" => {
+					formatted_text = format!("__{formatted_text}__");
+				}
+				"strike" | "strikethrough" => {
+					formatted_text = format!("~~{formatted_text}~~");
+				}
+				"code" => {
+					formatted_text = format!("`{formatted_text}`");
+				}
+				"link" => {
+					if let Some(href) = mark.attrs.as_ref().and_then(|attrs| attrs.href.as_ref()) {
+						formatted_text = format!("[{formatted_text}]({href})");
 					}
-					"link" => {}
-					_ => {}
 				}
+				_ => {}
 			}
 		}
-
-		output.push_str(&formatted_text);
 	}
+
+	formatted_text
 }
@@ -0,0 +1,154 @@
+use super::*;
+use crate::models::{
+	chapter::{LibGroupChapterData, LibGroupContentModel, LibGroupContentType},
+	responses::ChapterResponse,
+};
+use aidoku::{
+	alloc::{string::ToString, vec},
+	prelude::*,
+};
+use aidoku_test::aidoku_test;
+
+const IMAGE_BASE: &str = "https://img2.imglib.info";
+
+fn chapter_parts(json: &str) -> Vec<ContentPart> {
+	let response: ChapterResponse = serde_json::from_str(json).expect("valid chapter json");
+	match response.data {
+		Some(LibGroupChapterData::Text(chapter)) => match chapter.content {
+			LibGroupContentType::Model(model) => {
+				convert_model_to_parts(&model, &chapter.attachments, IMAGE_BASE)
+			}
+			LibGroupContentType::Html(_) => panic!("expected a model chapter"),
+		},
+		_ => panic!("expected a text chapter"),
+	}
+}
+
+fn model_parts(json: &str) -> Vec<ContentPart> {
+	let model: LibGroupContentModel = serde_json::from_str(json).expect("valid model json");
+	convert_model_to_parts(&model, &[], IMAGE_BASE)
+}
+
+/// The fixture is written by hand in the layout of `/api/manga/<slug>/chapter` responses,
+/// covering every node type the converter handles
+#[aidoku_test]
+fn ranobe_chapter_golden() {
+	let parts = chapter_parts(include_str!("fixtures/ranobe_chapter.json"));
+
+	assert_eq!(
+		parts,
+		vec![
+			ContentPart::Text(
+				"### Дорога на север\n\n\
+				Снег **не прекращался** третий день.\n\n\
+				*— Ты уверен?*  \n— Нет.[1]"
+					.to_string()
+			),
+			ContentPart::Image(
+				"https://img2.imglib.info/uploads/ranobe/north-road/chapters/2451837/c9f1e2.jpg"
+					.to_string()
+			),
+			ContentPart::Text(
+				"> Север помнит.\n>\n> Всегда.\n\n\
+				---\n\n\
+				| Имя | Ранг |\n| --- | --- |\n| Лин | A\\|B |\n\n\
+				- меч\n- [карта](https://ranobelib.me/ru/book/1)\n\n\
+				Конец главы[2]\n\n\
+				---\n\n\
+				1. Цитата из старой песни.\n\
+				2. Примечание переводчика."
+					.to_string()
+			),
+		]
+	);
+}
+
+#[aidoku_test]
+fn nested_images_stay_inline() {
+	let parts = model_parts(
+		r#"{"type":"doc","content":[
+			{"type":"blockquote","content":[
+				{"type":"image","attrs":{"images":[{"image":"https://cdn.example/a.png"}]}}
+			]},
+			{"type":"image","attrs":{"images":[{"image":"/uploads/b.png"}]}}
+		]}"#,
+	);
+
+	assert_eq!(
+		parts,
+		vec![
+			ContentPart::Text("> ![](https://cdn.example/a.png)".to_string()),
+			ContentPart::Image("https://img2.imglib.info/uploads/b.png".to_string()),
+		]
+	);
+}
+
+#[aidoku_test]
+fn heading_levels_are_clamped() {
+	let parts = model_parts(
+		r#"{"content":[
+			{"type":"heading","attrs":{"level":1},"content":[{"type":"text","text":"Том"}]},
+			{"type":"heading","attrs":{"level":9},"content":[{"type":"text","text":"Глубоко"}]},
+			{"type":"heading","content":[{"type":"text","text":"Без уровня"}]}
+		]}"#,
+	);
+
+	assert_eq!(
+		parts,
+		vec![ContentPart::Text(
+			"# Том\n\n###### Глубоко\n\n## Без уровня".to_string()
+		)]
+	);
+}
+
+#[aidoku_test]
+fn legacy_list_items_without_wrapper() {
+	let parts = model_parts(
+		r#"{"content":[
+			{"type":"orderedList","content":[
+				{"type":"text","text":"раз"},
+				{"type":"text","marks":[{"type":"italic"}],"text":"два"}
+			]}
+		]}"#,
+	);
+
+	assert_eq!(
+		parts,
+		vec![ContentPart::Text("1. раз\n2. *два*".to_string())]
+	);
+}
+
+#[aidoku_test]
+fn split_keeps_short_text_whole() {
+	assert_eq!(split_text_pages("a\n\nb", 100), vec!["a\n\nb".to_string()]);
+	assert!(split_text_pages("", 100).is_empty());
+	assert!(split_text_pages("\n\n  \n\n", 100).is_empty());
+}
+
+#[aidoku_test]
+fn split_at_paragraph_boundaries() {
+	let pages = split_text_pages("aaaa\n\nbbbb\n\ncccc", 10);
+
+	assert_eq!(pages, vec!["aaaa\n\nbbbb".to_string(), "cccc".to_string()]);
+}
+
+#[aidoku_test]
+fn split_keeps_long_paragraph_on_own_page() {
+	let long = "x".repeat(30);
+	let markdown = format!("short\n\n{long}\n\ntail");
+	let pages = split_text_pages(&markdown, 10);
+
+	assert_eq!(pages, vec!["short".to_string(), long, "tail".to_string()]);
+}
+
+#[aidoku_test]
+fn split_keeps_code_blocks_whole() {
+	let code = "```\nfn main() {\n\n    run();\n}\n```";
+	let markdown = format!("intro\n\n{code}\n\ntail");
+	let pages = split_text_pages(&markdown, 10);
+
+	assert_eq!(
+		pages,
+		vec!["intro".to_string(), code.to_string(), "tail".to_string()]
+	);
+}
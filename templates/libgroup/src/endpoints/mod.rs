@@ -1,8 +1,11 @@
 use aidoku::{
 	alloc::{String, Vec, string::ToString, vec},
+	helpers::uri::encode_uri_component,
 	prelude::*,
 };
 
+use crate::settings::TrendingPeriod;
+
 /// URL builder for API endpoints
 pub struct Url;
 
@@ -33,6 +36,14 @@ impl Url {
 		format!("{}/ru/manga/{}", Self::normalize_base(base_url), slug_url)
 	}
 
+	/// Build title page URL, RanobeLib keeps its novels under `/ru/book/`
+	pub fn title_page(site_id: u8, base_url: &str, slug_url: &str) -> String {
+		match site_id {
+			3 => format!("{}/ru/book/{}", Self::normalize_base(base_url), slug_url),
+			_ => Self::manga_page(base_url, slug_url),
+		}
+	}
+
 	/// Build manga chapters URL
 	pub fn manga_chapters(base_url: &str, slug_url: &str) -> String {
 		format!(
@@ -98,6 +109,78 @@ impl Url {
 		)
 	}
 
+	/// Build manga relations URL
+	pub fn manga_relations(base_url: &str, slug_url: &str) -> String {
+		format!(
+			"{}{}/manga/{}/relations",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH,
+			slug_url
+		)
+	}
+
+	/// Build manga stats URL with bookmark and like counters
+	pub fn manga_stats(base_url: &str, slug_url: &str) -> String {
+		let base = format!(
+			"{}{}/manga/{}/stats",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH,
+			slug_url
+		);
+		Self::append_query_params(base, &[("bookmarks", "true"), ("likes", "true")])
+	}
+
+	/// Build collection URL
+	pub fn collection(base_url: &str, collection_id: &str) -> String {
+		format!(
+			"{}{}/collections/{}",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH,
+			collection_id
+		)
+	}
+
+	/// Create search URL for people, publishers or teams by name
+	pub fn people_search(base_url: &str, path: &str, query: &str) -> String {
+		let base = format!(
+			"{}{}/{}",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH,
+			path
+		);
+		Self::append_query_params(base, &[("q", query)])
+	}
+
+	/// Default web domain of a LibGroup site
+	pub fn site_base(site_id: u8) -> Option<&'static str> {
+		match site_id {
+			1 => Some("https://mangalib.me"),
+			2 => Some("https://v2.shlib.life"),
+			3 => Some("https://ranobelib.me"),
+			4 => Some("https://hentailib.me"),
+			_ => None,
+		}
+	}
+
+	/// Build URL of the most upvoted comments of a chapter
+	pub fn chapter_comments(base_url: &str, chapter_id: &str) -> String {
+		let base = format!(
+			"{}{}/comments",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH
+		);
+		Self::append_query_params(
+			base,
+			&[
+				("page", "1"),
+				("post_id", chapter_id),
+				("post_type", "chapter"),
+				("sort_by", "votes_up"),
+				("sort_type", "desc"),
+			],
+		)
+	}
+
 	/// Build constants URL
 	pub fn constants(base_url: &str) -> String {
 		format!(
@@ -116,6 +199,15 @@ impl Url {
 		)
 	}
 
+	/// Build reading history URL for progress sync
+	pub fn reading_history(base_url: &str) -> String {
+		format!(
+			"{}{}/history",
+			Self::normalize_base(base_url),
+			Self::BASE_PATH
+		)
+	}
+
 	/// Create manga search URL with query parameters
 	pub fn manga_search_with_params(base_url: &str, params: &[(&str, &str)]) -> String {
 		let base = Self::manga_search(base_url);
@@ -168,12 +260,34 @@ impl Url {
 		Self::append_query_params(base, params)
 	}
 
+	/// Create "Сейчас читают" URL for the given period and popularity mode
+	pub fn currently_reading(
+		base_url: &str,
+		site_id: u8,
+		page: i32,
+		period: TrendingPeriod,
+		new_only: bool,
+	) -> String {
+		let page = page.to_string();
+		let site_id = site_id.to_string();
+		let mut params = vec![("page", page.as_str())];
+
+		if new_only {
+			params.push(("popularity", "1"));
+		}
+
+		params.push(("time", period.as_param()));
+		params.push(("site_id[]", site_id.as_str()));
+
+		Self::top_views_with_params(base_url, &params)
+	}
+
 	/// Normalize base URL by removing trailing slash
 	fn normalize_base(base_url: &str) -> &str {
 		base_url.trim_end_matches('/')
 	}
 
-	/// Append query parameters to URL
+	/// Append percent-encoded query parameters to URL
 	fn append_query_params(base_url: String, params: &[(&str, &str)]) -> String {
 		if params.is_empty() {
 			return base_url;
@@ -181,7 +295,13 @@ impl Url {
 
 		let query_string = params
 			.iter()
-			.map(|(key, value)| format!("{key}={value}"))
+			.map(|(key, value)| {
+				format!(
+					"{}={}",
+					encode_uri_component(key),
+					encode_uri_component(value)
+				)
+			})
 			.collect::<Vec<_>>()
 			.join("&");
 
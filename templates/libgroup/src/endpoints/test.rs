@@ -34,6 +34,33 @@ fn basic_urls() {
 		Url::auth_me(TEST_BASE_URL),
 		"https://api.cdnlibs.org/api/auth/me"
 	);
+
+	assert_eq!(
+		Url::manga_relations(TEST_BASE_URL, "test-manga"),
+		"https://api.cdnlibs.org/api/manga/test-manga/relations"
+	);
+
+	assert_eq!(
+		Url::collection(TEST_BASE_URL, "42"),
+		"https://api.cdnlibs.org/api/collections/42"
+	);
+
+	assert_eq!(
+		Url::reading_history(TEST_BASE_URL),
+		"https://api.cdnlibs.org/api/history"
+	);
+}
+
+#[aidoku_test]
+fn title_page_per_site() {
+	assert_eq!(
+		Url::title_page(1, "https://mangalib.me/", "1--slug"),
+		"https://mangalib.me/ru/manga/1--slug"
+	);
+	assert_eq!(
+		Url::title_page(3, "https://ranobelib.me", "1--slug"),
+		"https://ranobelib.me/ru/book/1--slug"
+	);
 }
 
 #[aidoku_test]
@@ -65,7 +92,7 @@ fn manga_search_with_params() {
 	assert!(url.contains("q=naruto"));
 	assert!(url.contains("buy=1"));
 	assert!(url.contains("licensed=1"));
-	assert!(url.contains("bookmarks_exclude[]=2"));
+	assert!(url.contains("bookmarks_exclude%5B%5D=2"));
 }
 
 #[aidoku_test]
@@ -78,7 +105,7 @@ fn manga_details_with_fields() {
 
 	assert_eq!(
 		url,
-		"https://api.cdnlibs.org/api/manga/test-manga?fields[]=summary&fields[]=tags&fields[]=authors&fields[]=artists"
+		"https://api.cdnlibs.org/api/manga/test-manga?fields%5B%5D=summary&fields%5B%5D=tags&fields%5B%5D=authors&fields%5B%5D=artists"
 	);
 }
 
@@ -121,24 +148,66 @@ fn constants_with_fields() {
 	);
 
 	assert!(url.starts_with("https://api.cdnlibs.org/api/constants?"));
-	assert!(url.contains("fields[]=genres"));
-	assert!(url.contains("fields[]=tags"));
-	assert!(url.contains("fields[]=scanlateStatus"));
-	assert!(url.contains("fields[]=status"));
-	assert!(url.contains("fields[]=format"));
-	assert!(url.contains("fields[]=ageRestriction"));
-	assert!(url.contains("fields[]=imageServers"));
+	assert!(url.contains("fields%5B%5D=genres"));
+	assert!(url.contains("fields%5B%5D=tags"));
+	assert!(url.contains("fields%5B%5D=scanlateStatus"));
+	assert!(url.contains("fields%5B%5D=status"));
+	assert!(url.contains("fields%5B%5D=format"));
+	assert!(url.contains("fields%5B%5D=ageRestriction"));
+	assert!(url.contains("fields%5B%5D=imageServers"));
 
 	let image_servers_url = Url::constants_with_fields(TEST_BASE_URL, &["imageServers"]);
 	assert_eq!(
 		image_servers_url,
-		"https://api.cdnlibs.org/api/constants?fields[]=imageServers"
+		"https://api.cdnlibs.org/api/constants?fields%5B%5D=imageServers"
 	);
 
 	let empty_url = Url::constants_with_fields(TEST_BASE_URL, &[]);
 	assert_eq!(empty_url, "https://api.cdnlibs.org/api/constants");
 }
 
+#[aidoku_test]
+fn currently_reading_url() {
+	let url = Url::currently_reading(TEST_BASE_URL, 1, 2, TrendingPeriod::Week, true);
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/media/top-views?page=2&popularity=1&time=week&site_id%5B%5D=1"
+	);
+
+	let all_titles = Url::currently_reading(TEST_BASE_URL, 3, 1, TrendingPeriod::All, false);
+	assert_eq!(
+		all_titles,
+		"https://api.cdnlibs.org/api/media/top-views?page=1&time=all&site_id%5B%5D=3"
+	);
+}
+
+#[aidoku_test]
+fn people_search_url() {
+	let url = Url::people_search(TEST_BASE_URL, "people", "Ода Эйитиро");
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/people?q=%D0%9E%D0%B4%D0%B0%20%D0%AD%D0%B9%D0%B8%D1%82%D0%B8%D1%80%D0%BE"
+	);
+}
+
+#[aidoku_test]
+fn manga_stats_url() {
+	let url = Url::manga_stats(TEST_BASE_URL, "206--one-piece");
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/manga/206--one-piece/stats?bookmarks=true&likes=true"
+	);
+}
+
+#[aidoku_test]
+fn chapter_comments_url() {
+	let url = Url::chapter_comments(TEST_BASE_URL, "2451837");
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/comments?page=1&post_id=2451837&post_type=chapter&sort_by=votes_up&sort_type=desc"
+	);
+}
+
 #[aidoku_test]
 fn trailing_slash_handling() {
 	let base_with_slash = "https://api.example.com/";
@@ -150,3 +219,39 @@ fn trailing_slash_handling() {
 	assert_eq!(url1, url2);
 	assert_eq!(url1, "https://api.example.com/api/manga");
 }
+
+#[aidoku_test]
+fn query_params_encode_cyrillic() {
+	let url = Url::manga_search_with_params(
+		TEST_BASE_URL,
+		&[("q", "Поднятие уровня"), ("site_id[]", "1")],
+	);
+
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/manga?q=%D0%9F%D0%BE%D0%B4%D0%BD%D1%8F%D1%82%D0%B8%D0%B5%20%D1%83%D1%80%D0%BE%D0%B2%D0%BD%D1%8F&site_id%5B%5D=1"
+	);
+}
+
+#[aidoku_test]
+fn query_params_encode_cjk() {
+	let url = Url::manga_search_with_params(TEST_BASE_URL, &[("q", "進撃の巨人")]);
+
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/manga?q=%E9%80%B2%E6%92%83%E3%81%AE%E5%B7%A8%E4%BA%BA"
+	);
+}
+
+#[aidoku_test]
+fn query_params_encode_reserved_characters() {
+	let url = Url::manga_search_with_params(TEST_BASE_URL, &[("q", "a&b=c #1 + d?/%")]);
+
+	assert_eq!(
+		url,
+		"https://api.cdnlibs.org/api/manga?q=a%26b%3Dc%20%231%20%2B%20d%3F%2F%25"
+	);
+	// Reserved characters must not leak as extra parameters
+	assert_eq!(url.matches('&').count(), 0);
+	assert_eq!(url.matches('=').count(), 1);
+}
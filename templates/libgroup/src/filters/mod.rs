@@ -1,10 +1,20 @@
 use aidoku::{
 	FilterValue,
-	alloc::{String, Vec, string::ToString},
+	alloc::{String, Vec, string::ToString, vec},
 	imports::std::current_date,
 };
 use chrono::{DateTime, Datelike, Utc};
 
+use crate::people::PeopleKind;
+
+/// Index of "По рейтингу" in the sort filter
+const SORT_BY_RATING: i32 = 1;
+/// Index of "Дате обновления" in the sort filter
+const SORT_BY_UPDATE: i32 = 5;
+
+/// Votes a title needs to be listed as top rated, so a single 10/10 vote doesn't top the list
+const TOP_RATED_MIN_VOTES: f32 = 100.0;
+
 #[derive(Clone)]
 pub enum FilterId {
 	Sort,
@@ -52,6 +62,45 @@ impl FilterProcessor {
 		Self
 	}
 
+	/// Filters behind the top rated listing, the same ones a user would pick in search
+	pub fn top_rated_filters() -> Vec<FilterValue> {
+		vec![
+			FilterValue::Sort {
+				id: "sort".into(),
+				index: SORT_BY_RATING,
+				ascending: false,
+			},
+			FilterValue::Range {
+				id: "rate_count".into(),
+				from: Some(TOP_RATED_MIN_VOTES),
+				to: None,
+			},
+		]
+	}
+
+	/// Filters behind the latest updates listing
+	pub fn latest_filters() -> Vec<FilterValue> {
+		vec![FilterValue::Sort {
+			id: "sort".into(),
+			index: SORT_BY_UPDATE,
+			ascending: false,
+		}]
+	}
+
+	/// `caution[]` parameters for the source's default age ratings, unless some are already chosen
+	pub fn default_age_rating_params(
+		defaults: &[&str],
+		params: &[(&'static str, String)],
+	) -> Vec<(&'static str, String)> {
+		if params.iter().any(|(key, _)| *key == "caution[]") {
+			return Vec::new();
+		}
+		defaults
+			.iter()
+			.map(|rating| ("caution[]", rating.to_string()))
+			.collect()
+	}
+
 	pub fn process_filters(&self, filters: Vec<FilterValue>) -> Vec<(&'static str, String)> {
 		filters
 			.into_iter()
@@ -70,11 +119,11 @@ impl FilterProcessor {
 				let id_enum = FilterId::from(id.as_str());
 				if let FilterId::Sort = id_enum {
 					match index {
-						1 => params.push(("sort_by", "rate_avg".to_string())),
+						SORT_BY_RATING => params.push(("sort_by", "rate_avg".to_string())),
 						2 => params.push(("sort_by", "views".to_string())),
 						3 => params.push(("sort_by", "chap_count".to_string())),
 						4 => params.push(("sort_by", "releaseDate".to_string())),
-						5 => params.push(("sort_by", "last_chapter_at".to_string())),
+						SORT_BY_UPDATE => params.push(("sort_by", "last_chapter_at".to_string())),
 						6 => params.push(("sort_by", "created_at".to_string())),
 						7 => params.push(("sort_by", "name".to_string())),
 						8 => params.push(("sort_by", "rus_name".to_string())),
@@ -135,6 +184,16 @@ impl FilterProcessor {
 					_ => {}
 				}
 			}
+			FilterValue::Text { id, value } => {
+				// Names are resolved to ids before filters are processed
+				if let Some(kind) = PeopleKind::from_filter_id(&id)
+					&& !value.trim().is_empty()
+				{
+					for key in kind.query_keys() {
+						params.push((*key, value.trim().to_string()));
+					}
+				}
+			}
 			FilterValue::Range { id, from, to } => {
 				let id_enum = FilterId::from(id.as_str());
 				match id_enum {
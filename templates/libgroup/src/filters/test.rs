@@ -121,3 +121,72 @@ fn test_range_filter_basic() {
 	assert!(result.contains(&("chap_count_max", "50".to_string())));
 	assert!(result.contains(&("year_min", "2025".to_string())));
 }
+
+#[aidoku_test]
+fn test_people_text_filters() {
+	let processor = create_processor();
+	let filters = vec![
+		FilterValue::Text {
+			id: "author".to_string(),
+			value: "12".to_string(),
+		},
+		FilterValue::Text {
+			id: "team".to_string(),
+			value: " 34 ".to_string(),
+		},
+		FilterValue::Text {
+			id: "publisher".to_string(),
+			value: "".to_string(),
+		},
+	];
+
+	let result = processor.process_filters(filters);
+	assert_eq!(result.len(), 2);
+	assert!(result.contains(&("authors[]", "12".to_string())));
+	assert!(result.contains(&("teams[]", "34".to_string())));
+}
+
+#[aidoku_test]
+fn test_top_rated_filters_match_search() {
+	let processor = create_processor();
+	let result = processor.process_filters(FilterProcessor::top_rated_filters());
+
+	assert_eq!(
+		result,
+		vec![
+			("sort_by", "rate_avg".to_string()),
+			("rate_min", "100".to_string()),
+		]
+	);
+}
+
+#[aidoku_test]
+fn test_latest_filters_sort_by_update() {
+	let processor = create_processor();
+	let result = processor.process_filters(FilterProcessor::latest_filters());
+
+	assert_eq!(result, vec![("sort_by", "last_chapter_at".to_string())]);
+}
+
+#[aidoku_test]
+fn test_default_age_ratings_fill_missing_caution() {
+	let params = vec![("page", "1".to_string())];
+	let result = FilterProcessor::default_age_rating_params(&["3", "4", "5"], &params);
+
+	assert_eq!(
+		result,
+		vec![
+			("caution[]", "3".to_string()),
+			("caution[]", "4".to_string()),
+			("caution[]", "5".to_string()),
+		]
+	);
+}
+
+#[aidoku_test]
+fn test_default_age_ratings_respect_user_choice() {
+	let params = vec![("caution[]", "3".to_string())];
+
+	assert!(FilterProcessor::default_age_rating_params(&["3", "4", "5"], &params).is_empty());
+	assert!(FilterProcessor::default_age_rating_params(&[], &[]).is_empty());
+}
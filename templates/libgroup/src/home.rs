@@ -1,133 +1,215 @@
 use aidoku::{
-	HomeComponent, HomeComponentValue, HomeLayout, HomePartialResult, Link, Listing, ListingKind,
-	Manga, Result,
-	alloc::{Vec, string::ToString, vec},
+	AidokuError, HomeComponent, HomeComponentValue, HomeLayout, HomePartialResult, Link, Listing,
+	ListingKind, Manga, Result,
+	alloc::{String, Vec, string::ToString, vec},
 	imports::{net::Request, std::send_partial_result},
+	prelude::*,
 };
 
 use crate::{
-	auth::AuthRequest,
+	auth::send_all_authed,
 	context::Context,
 	endpoints::Url,
-	models::responses::{MangaDetailResponse, MangaListResponse},
+	filters::FilterProcessor,
+	imp::search_url,
+	models::{
+		manga::LibGroupManga,
+		responses::{MangaDetailResponse, MangaListResponse},
+	},
+	settings::{get_trending_new_only, get_trending_period},
 };
 
+use super::SiteListing;
+
 const POPULAR_TITLE: &str = "Популярное";
 const POPULAR_SUBTITLE: &str = "За всё время";
 const TRENDING_TITLE: &str = "Сейчас читают";
-const TRENDING_SUBTITLE: &str = "Новинки дня";
 const LATEST_TITLE: &str = "Последние обновления";
 
-// Send initial layout structure
-pub fn send_initial_layout() {
-	send_partial_result(&HomePartialResult::Layout(HomeLayout {
-		components: vec![
-			create_home_component(
-				POPULAR_TITLE,
-				Some(POPULAR_SUBTITLE),
-				HomeComponentValue::empty_big_scroller(),
-			),
-			create_home_component(
-				TRENDING_TITLE,
-				Some(TRENDING_SUBTITLE),
-				HomeComponentValue::empty_scroller(),
-			),
-			create_home_component(LATEST_TITLE, None, HomeComponentValue::empty_scroller()),
-		],
-	}));
+/// Titles in the popular big scroller, each with a details request
+const POPULAR_COUNT: usize = 10;
+/// Titles in the regular scrollers
+const SCROLLER_COUNT: usize = 30;
+
+// Send initial layout structure, site sections go after the shared ones
+pub fn send_initial_layout(site_listings: &[SiteListing]) {
+	let mut components = vec![
+		create_home_component(
+			POPULAR_TITLE,
+			Some(POPULAR_SUBTITLE),
+			HomeComponentValue::empty_big_scroller(),
+		),
+		create_home_component(
+			TRENDING_TITLE,
+			Some(&trending_subtitle()),
+			HomeComponentValue::empty_scroller(),
+		),
+		create_home_component(LATEST_TITLE, None, HomeComponentValue::empty_scroller()),
+	];
+	components.extend(
+		site_listings
+			.iter()
+			.filter(|listing| listing.on_home)
+			.map(|listing| {
+				create_home_component(listing.name, None, HomeComponentValue::empty_scroller())
+			}),
+	);
+
+	send_partial_result(&HomePartialResult::Layout(HomeLayout { components }));
 }
 
-// Load popular manga with detailed information
-pub fn load_popular_manga(ctx: &Context) -> Result<()> {
+// Load popular, trending and latest titles in one batch, send them as they are,
+// then refine the popular big scroller with details fetched in a second batch
+pub fn load_shared_sections(ctx: &Context, default_age_ratings: &[&str]) -> Result<()> {
 	let site_id_str = ctx.site_id.to_string();
-	let params = vec![("site_id[]", site_id_str.as_str())];
-	let url = Url::manga_search_with_params(&ctx.api_url, &params);
+	let popular_url = search_url(
+		ctx,
+		default_age_ratings,
+		vec![("site_id[]", site_id_str.clone())],
+		Vec::new(),
+	);
+	let trending_url = Url::currently_reading(
+		&ctx.api_url,
+		ctx.site_id,
+		1,
+		get_trending_period(),
+		get_trending_new_only(),
+	);
+	let latest_url = search_url(
+		ctx,
+		default_age_ratings,
+		vec![("page", "1".into()), ("site_id[]", site_id_str)],
+		FilterProcessor::latest_filters(),
+	);
+
+	let mut responses = send_all_authed(
+		[
+			Request::get(popular_url)?,
+			Request::get(trending_url)?,
+			Request::get(latest_url)?,
+		],
+		ctx,
+	)
+	.into_iter()
+	.map(|response| {
+		response
+			.ok()
+			.and_then(|response| response.get_json::<MangaListResponse>().ok())
+			.map(|list| list.data)
+	});
+	let popular = responses.next().flatten();
+	let trending = responses.next().flatten();
+	let latest = responses.next().flatten();
 
-	let response = Request::get(&url)?
-		.authed(ctx)?
-		.get_json::<MangaListResponse>()?;
+	if popular.is_none() && trending.is_none() && latest.is_none() {
+		return Err(AidokuError::message("Failed to load home"));
+	}
 
-	let entries: Vec<Manga> = response
-		.data
+	let popular: Vec<LibGroupManga> = popular
+		.unwrap_or_default()
 		.into_iter()
-		.take(10)
-		.map(|manga_data| {
-			// Try to fetch details, fallback to basic if it fails
-			fetch_manga_details(&manga_data.slug_url, ctx)
-				.unwrap_or_else(|_| manga_data.into_manga(ctx))
-		})
+		.take(POPULAR_COUNT)
 		.collect();
+	if !popular.is_empty() {
+		send_popular_component(
+			popular
+				.iter()
+				.cloned()
+				.map(|manga_data| manga_data.into_manga(ctx))
+				.collect(),
+		);
+	}
 
-	send_popular_component(entries);
-	Ok(())
-}
+	if let Some(trending) = trending {
+		send_scroller_component(
+			TRENDING_TITLE,
+			Some(&trending_subtitle()),
+			into_links(trending, ctx),
+			"currently_reading",
+			TRENDING_TITLE,
+		);
+	}
 
-// Load currently reading manga (daily trending)
-pub fn load_currently_reading(ctx: &Context) -> Result<()> {
-	let params = vec![("page", "1"), ("popularity", "1"), ("time", "day")];
-	let url = Url::top_views_with_params(&ctx.api_url, &params);
+	if let Some(latest) = latest {
+		send_scroller_component(
+			LATEST_TITLE,
+			None,
+			into_links(latest, ctx),
+			"latest",
+			LATEST_TITLE,
+		);
+	}
 
-	let response = Request::get(url)?
-		.authed(ctx)?
-		.get_json::<MangaListResponse>()?;
+	if !popular.is_empty() {
+		send_popular_component(with_details(popular, ctx));
+	}
 
-	let entries: Vec<Link> = response
-		.data
+	Ok(())
+}
+
+// Send a site section loaded by `Impl::get_site_listing`
+pub fn send_site_section(listing: &SiteListing, entries: Vec<Manga>) {
+	let entries: Vec<Link> = entries
 		.into_iter()
-		.take(30)
-		.map(|manga_data| Link::from(manga_data.into_manga(ctx)))
+		.take(SCROLLER_COUNT)
+		.map(Link::from)
 		.collect();
 
-	send_scroller_component(
-		TRENDING_TITLE,
-		Some(TRENDING_SUBTITLE),
-		entries,
-		"currently_reading",
-		TRENDING_TITLE,
-	);
-	Ok(())
+	send_scroller_component(listing.name, None, entries, listing.id, listing.name);
 }
 
-// Load latest updates
-pub fn load_latest_updates(ctx: &Context) -> Result<()> {
-	let site_id_str = &ctx.site_id.to_string();
-	let params = vec![
-		("page", "1"),
-		("site_id[]", site_id_str.as_str()),
-		("sort_by", "last_chapter_at"),
-	];
-	let url = Url::manga_search_with_params(&ctx.api_url, &params);
-
-	let response = Request::get(url)?
-		.authed(ctx)?
-		.get_json::<MangaListResponse>()?;
+// Helper functions
+fn trending_subtitle() -> String {
+	let mode = if get_trending_new_only() {
+		"Новинки"
+	} else {
+		"Все тайтлы"
+	};
+	format!("{mode} {}", get_trending_period().title())
+}
 
-	let entries: Vec<Link> = response
-		.data
+fn into_links(entries: Vec<LibGroupManga>, ctx: &Context) -> Vec<Link> {
+	entries
 		.into_iter()
-		.take(30)
+		.take(SCROLLER_COUNT)
 		.map(|manga_data| Link::from(manga_data.into_manga(ctx)))
-		.collect();
-
-	send_scroller_component(LATEST_TITLE, None, entries, "latest", LATEST_TITLE);
-	Ok(())
+		.collect()
 }
 
-// Helper functions
-fn fetch_manga_details(slug_url: &str, ctx: &Context) -> Result<Manga> {
-	let details_url = Url::manga_details_with_fields(
-		&ctx.api_url,
-		slug_url,
-		&["summary", "tags", "authors", "artists"],
-	);
+// Fetch details for all entries at once, keeping the basic entry when a request fails
+fn with_details(entries: Vec<LibGroupManga>, ctx: &Context) -> Vec<Manga> {
+	let requests: Vec<Request> = entries
+		.iter()
+		.filter_map(|manga_data| {
+			let details_url = Url::manga_details_with_fields(
+				&ctx.api_url,
+				&manga_data.slug_url,
+				&["summary", "tags", "authors", "artists"],
+			);
+			Request::get(details_url).ok()
+		})
+		.collect();
 
-	let manga = Request::get(details_url)?
-		.authed(ctx)?
-		.get_json::<MangaDetailResponse>()?
-		.data
-		.into_manga(ctx);
+	if requests.len() != entries.len() {
+		return entries
+			.into_iter()
+			.map(|manga_data| manga_data.into_manga(ctx))
+			.collect();
+	}
 
-	Ok(manga)
+	entries
+		.into_iter()
+		.zip(send_all_authed(requests, ctx))
+		.map(|(manga_data, response)| {
+			response
+				.ok()
+				.filter(|response| (200..300).contains(&response.status_code()))
+				.and_then(|response| response.get_json::<MangaDetailResponse>().ok())
+				.map(|details| details.data)
+				.unwrap_or(manga_data)
+				.into_manga(ctx)
+		})
+		.collect()
 }
 
 fn create_home_component(
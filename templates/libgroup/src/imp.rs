@@ -1,27 +1,51 @@
 use aidoku::{
-	AidokuError, Chapter, FilterValue, HomeLayout, Listing, Manga, MangaPageResult, Page,
-	PageContext, Result,
+	AidokuError, Chapter, ContentRating, DeepLinkResult, FilterValue, HomeLayout, Listing,
+	ListingKind, Manga, MangaPageResult, Page, PageContext, Result,
 	alloc::{String, Vec, string::ToString, vec},
-	imports::{net::Request, std::send_partial_result},
+	imports::{
+		net::{Request, Response},
+		std::send_partial_result,
+	},
+	prelude::*,
 };
 
 use crate::{
-	auth::{AuthRequest, USER_AGENT, clear_user_id, get_user_id},
+	auth::{AuthRequest, USER_AGENT, clear_user_id, get_user_id, is_logged_in, send_all_authed},
+	bookmarks::{BookmarkFolder, bookmark_listings},
+	cdn::get_image_url,
 	chapters::get_chapters_cache,
+	comments,
 	context::Context,
 	endpoints::Url,
 	filters::FilterProcessor,
 	home,
+	links::{LibGroupLink, find_branch, parse_link},
 	models::{
-		chapter::LibGroupChapterListItem,
+		chapter::{LibGroupChapterBranch, LibGroupChapterListItem, LockReason},
+		manga::LibGroupRelation,
+		requests::ReadingProgressRequest,
 		responses::{
-			ChapterResponse, ChaptersResponse, MangaCoversResponse, MangaDetailResponse,
-			MangaListResponse,
+			ChapterResponse, ChaptersResponse, CollectionResponse, MangaCoversResponse,
+			MangaDetailResponse, MangaListResponse, MangaStatsResponse, RelationsResponse,
 		},
 	},
+	people::{PeopleKind, listing_name, resolve_id},
+	settings::{
+		get_branch_preference, get_chapter_comments_enabled, get_hide_locked_chapters,
+		get_sync_progress_enabled, get_trending_new_only, get_trending_period,
+	},
 };
 
-use super::Params;
+use super::{Params, SiteListing};
+
+/// How long a chapter list is reused before it's fetched again when opening chapters
+const CHAPTERS_TTL: i64 = 3600;
+
+/// Listing id prefix for the members of a collection, e.g. `collection-<id>`, opened from collection links
+pub const COLLECTION_LISTING_PREFIX: &str = "collection-";
+
+/// Listing id prefix for the related titles of a manga, e.g. `relations-<slug_url>`
+pub const RELATIONS_LISTING_PREFIX: &str = "relations-";
 
 pub trait Impl {
 	fn new() -> Self;
@@ -47,30 +71,43 @@ pub trait Impl {
 		query_params.push(("page", page.to_string()));
 		query_params.push(("site_id[]", ctx.site_id.to_string()));
 
-		let filter_processor = FilterProcessor::new();
-		query_params.extend(filter_processor.process_filters(filters));
-
-		let params_for_url: Vec<(&str, &str)> =
-			query_params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+		// Author, artist, publisher and team filters accept names, but the API wants ids
+		let mut resolved_filters = Vec::with_capacity(filters.len());
+		for filter in filters {
+			match filter {
+				FilterValue::Text { id, value } => {
+					let kind = PeopleKind::from_filter_id(&id)
+						.ok_or_else(|| AidokuError::message("Invalid text filter id"))?;
+					if value.trim().is_empty() {
+						continue;
+					}
+					let Some(people_id) = resolve_id(&ctx, kind, &value)? else {
+						// Nobody with that name, so nothing can match
+						return Ok(MangaPageResult {
+							entries: Vec::new(),
+							has_next_page: false,
+						});
+					};
+					resolved_filters.push(FilterValue::Text {
+						id,
+						value: people_id,
+					});
+				}
+				filter => resolved_filters.push(filter),
+			}
+		}
 
-		let search_url = Url::manga_search_with_params(&ctx.api_url, &params_for_url);
+		let search_url = search_url(
+			&ctx,
+			&params.default_age_ratings,
+			query_params,
+			resolved_filters,
+		);
 
-		let response = Request::get(search_url)?
+		Ok(Request::get(search_url)?
 			.authed(&ctx)?
-			.get_json::<MangaListResponse>()?;
-
-		let entries: Vec<Manga> = response
-			.data
-			.into_iter()
-			.map(|manga_lib_manga| manga_lib_manga.into_manga(&ctx))
-			.collect();
-
-		let has_next_page = response.meta.has_next_page.unwrap_or_default();
-
-		Ok(MangaPageResult {
-			entries,
-			has_next_page,
-		})
+			.get_json::<MangaListResponse>()?
+			.into_manga_page_result(&ctx))
 	}
 
 	fn get_manga_update(
@@ -95,15 +132,33 @@ pub trait Impl {
 					"artists",
 					"otherNames",
 					"rate_avg",
+					"rate",
+					"views",
 				],
 			);
-			manga.copy_from(
-				Request::get(details_url)?
-					.authed(&ctx)?
-					.get_json::<MangaDetailResponse>()?
-					.data
-					.into_manga(&ctx),
-			);
+			let [details, relations, stats]: [Result<Response>; 3] = send_all_authed(
+				[
+					Request::get(details_url)?,
+					Request::get(Url::manga_relations(&ctx.api_url, &slug_url))?,
+					Request::get(Url::manga_stats(&ctx.api_url, &slug_url))?,
+				],
+				&ctx,
+			)
+			.try_into()
+			.map_err(|_| AidokuError::message("Failed to load details"))?;
+
+			let mut details = details?.get_json::<MangaDetailResponse>()?.data;
+			// Relations and counters are optional, a failure shouldn't hide the details
+			details.relations = relations
+				.ok()
+				.and_then(|response| response.get_json::<RelationsResponse>().ok())
+				.map(|response| response.data);
+			details.stats = stats
+				.ok()
+				.and_then(|response| response.get_json::<MangaStatsResponse>().ok())
+				.map(|response| response.data);
+
+			manga.copy_from(details.into_manga(&ctx));
 
 			if needs_chapters {
 				send_partial_result(&manga);
@@ -112,16 +167,24 @@ pub trait Impl {
 
 		if needs_chapters {
 			let chapters_url = Url::manga_chapters(ctx.api_url.as_str(), &slug_url);
+			let items = Request::get(chapters_url)?
+				.authed(&ctx)?
+				.get_json::<ChaptersResponse>()?
+				.data;
 
-			let chapters = LibGroupChapterListItem::flatten_chapters(
-				Request::get(chapters_url)?
-					.authed(&ctx)?
-					.get_json::<ChaptersResponse>()?
-					.data,
+			// Opening a chapter right after a refresh shouldn't need another request
+			get_chapters_cache().insert(&slug_url, items.clone());
+
+			let mut chapters = LibGroupChapterListItem::flatten_chapters(
+				items,
 				ctx.base_url.as_str(),
 				&slug_url,
 				&user_id,
+				&get_branch_preference(),
 			);
+			if get_hide_locked_chapters() {
+				chapters.retain(|chapter| !chapter.locked);
+			}
 
 			manga.chapters = Some(chapters);
 		}
@@ -136,12 +199,12 @@ pub trait Impl {
 		let chapter_number = chapter.chapter_number.unwrap_or_default();
 		let volume = chapter.volume_number.unwrap_or_default();
 
-		let chapters = get_chapters_cache(Some(3600)).get_chapters(slug_url, &ctx)?;
-		let branch_id: Option<i32> = chapters
+		let chapters = get_chapters_cache().get_chapters(slug_url, &ctx, Some(CHAPTERS_TTL))?;
+		let branch = chapters
 			.into_iter()
 			.flat_map(|c| c.branches)
-			.find(|branch| branch.id.to_string() == chapter.key)
-			.and_then(|branch| branch.branch_id);
+			.find(|branch| branch.id.to_string() == chapter.key);
+		let branch_id = branch.as_ref().and_then(|branch| branch.branch_id);
 
 		let pages_url = Url::chapter_pages_with_params(
 			&ctx.api_url,
@@ -151,27 +214,130 @@ pub trait Impl {
 			volume,
 		);
 
-		let pages = Request::get(pages_url)?
-			.authed(&ctx)?
-			.get_json::<ChapterResponse>()?
-			.data
-			.ok_or_else(|| AidokuError::message("Chapter is empty"))?
-			.into_pages(&ctx);
+		let pages_request = Request::get(pages_url)?;
+		// Progress sync is best effort: it's sent along with the pages so reading isn't delayed,
+		// and its result is ignored
+		let progress_request = get_sync_progress_enabled()
+			.then(|| self.reading_progress_request(&ctx, &manga, &chapter, branch_id))
+			.flatten();
+		let data = match progress_request {
+			Some(progress_request) => send_all_authed([pages_request, progress_request], &ctx)
+				.into_iter()
+				.next()
+				.ok_or_else(|| AidokuError::message("Missing response"))??,
+			None => pages_request.authed(&ctx)?,
+		}
+		.get_json::<ChapterResponse>()?
+		.data
+		.filter(|data| !data.is_empty())
+		.ok_or_else(|| chapter_lock_error(branch.as_ref(), &manga))?;
+		let note = data.note().map(String::from);
+		let mut pages = data.into_pages(&ctx);
+
+		if get_chapter_comments_enabled() {
+			comments::attach_description(&mut pages, &chapter.key, note);
+		}
 
 		Ok(pages)
 	}
 
+	/// Request telling the site that a chapter was opened, so its "continue reading" state stays
+	/// in sync. `None` for anonymous users.
+	fn reading_progress_request(
+		&self,
+		ctx: &Context,
+		manga: &Manga,
+		chapter: &Chapter,
+		branch_id: Option<i32>,
+	) -> Option<Request> {
+		let user_id = get_user_id(ctx)?;
+		let chapter_id = chapter.key.parse::<i32>().ok()?;
+
+		let body = serde_json::to_string(&ReadingProgressRequest {
+			user_id,
+			media_slug: manga.key.clone(),
+			chapter_id,
+			branch_id,
+		})
+		.ok()?;
+
+		Request::post(Url::reading_history(&ctx.api_url))
+			.ok()
+			.map(|request| {
+				request
+					.header("Content-Type", "application/json")
+					.body(body)
+			})
+	}
+
+	/// Team note and top comments of the chapter, shown after its last page
+	fn get_page_description(&self, params: &Params, page: Page) -> Result<String> {
+		let ctx = Context::from_params(params);
+		comments::get_page_description(&ctx, &page)
+	}
+
 	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
 		let ctx = Context::from_params(params);
 
-		home::send_initial_layout();
-		home::load_popular_manga(&ctx)?;
-		home::load_currently_reading(&ctx)?;
-		home::load_latest_updates(&ctx)?;
+		home::send_initial_layout(&params.site_listings);
+		home::load_shared_sections(&ctx, &params.default_age_ratings)?;
+
+		// Site sections are loaded together, one failing only leaves its scroller empty
+		let listings: Vec<&SiteListing> = params
+			.site_listings
+			.iter()
+			.filter(|listing| listing.on_home)
+			.collect();
+		let requests = listings
+			.iter()
+			.map(|listing| Request::get(self.site_listing_url(params, listing, 1)))
+			.collect::<Result<Vec<Request>>>()?;
+
+		for (listing, response) in listings.into_iter().zip(send_all_authed(requests, &ctx)) {
+			if let Some(result) = response
+				.ok()
+				.and_then(|response| response.get_json::<MangaListResponse>().ok())
+			{
+				home::send_site_section(listing, result.into_manga_page_result(&ctx).entries);
+			}
+		}
 
 		Ok(HomeLayout::default())
 	}
 
+	/// Catalog URL for a page of a listing declared in `Params::site_listings`.
+	/// Sites can override this for listings that need another catalog query.
+	fn site_listing_url(&self, params: &Params, listing: &SiteListing, page: i32) -> String {
+		let ctx = Context::from_params(params);
+		let mut query_params: Vec<(&'static str, String)> = vec![
+			("page", page.to_string()),
+			("site_id[]", ctx.site_id.to_string()),
+		];
+		query_params.extend(
+			listing
+				.query
+				.iter()
+				.map(|(key, value)| (*key, value.to_string())),
+		);
+
+		search_url(&ctx, &params.default_age_ratings, query_params, Vec::new())
+	}
+
+	/// Load a page of a listing declared in `Params::site_listings`
+	fn get_site_listing(
+		&self,
+		params: &Params,
+		listing: &SiteListing,
+		page: i32,
+	) -> Result<MangaPageResult> {
+		let ctx = Context::from_params(params);
+
+		Ok(Request::get(self.site_listing_url(params, listing, page))?
+			.authed(&ctx)?
+			.get_json::<MangaListResponse>()?
+			.into_manga_page_result(&ctx))
+	}
+
 	fn get_manga_list(
 		&self,
 		params: &Params,
@@ -184,86 +350,141 @@ pub trait Impl {
 
 		match listing.id.as_str() {
 			"popular" => {
-				// Popular manga
-				let popular_params: Vec<(&str, &str)> =
-					vec![("page", page_str.as_str()), ("site_id[]", &site_id_str)];
-
-				let popular_url = Url::manga_search_with_params(&ctx.api_url, &popular_params);
-
-				let response = Request::get(popular_url)?
-					.authed(&ctx)?
-					.get_json::<MangaListResponse>()?;
-
-				let entries: Vec<Manga> = response
-					.data
-					.into_iter()
-					.map(|manga_lib_manga| manga_lib_manga.into_manga(&ctx))
-					.collect();
-
-				let has_next_page = response.meta.has_next_page.unwrap_or_default();
-
-				Ok(MangaPageResult {
-					entries,
-					has_next_page,
-				})
+				// Popular manga, the catalog's default order
+				self.get_search_manga_list(params, None, page, Vec::new())
 			}
 			"currently_reading" => {
-				// "Сейчас читают" with configurable parameters
-				let params: Vec<(&str, &str)> = vec![
-					("page", page_str.as_str()),
-					("popularity", "1"), // Default to "Новинки"
-					("time", "day"),     // Default to "За день"
-					("site_id[]", &site_id_str),
-				];
-
-				let currently_reading_url = Url::top_views_with_params(&ctx.api_url, &params);
-
-				let response = Request::get(currently_reading_url)?
+				// "Сейчас читают" with the period and popularity mode from settings
+				let currently_reading_url = Url::currently_reading(
+					&ctx.api_url,
+					ctx.site_id,
+					page,
+					get_trending_period(),
+					get_trending_new_only(),
+				);
+
+				Ok(Request::get(currently_reading_url)?
 					.authed(&ctx)?
-					.get_json::<MangaListResponse>()?;
-
-				let entries: Vec<Manga> = response
-					.data
-					.into_iter()
-					.map(|manga_lib_manga| manga_lib_manga.into_manga(&ctx))
-					.collect();
-
-				let has_next_page = response.meta.has_next_page.unwrap_or_default();
-
-				Ok(MangaPageResult {
-					entries,
-					has_next_page,
-				})
+					.get_json::<MangaListResponse>()?
+					.into_manga_page_result(&ctx))
+			}
+			"top_rated" => {
+				// Same request as sorting the search by rating with a minimum vote count
+				self.get_search_manga_list(params, None, page, FilterProcessor::top_rated_filters())
 			}
 			"latest" => {
-				// Latest updates
-				let latest_params: Vec<(&str, &str)> = vec![
-					("page", page_str.as_str()),
-					("site_id[]", site_id_str.as_str()),
-					("sort_by", "last_chapter_at"),
-				];
+				// Latest updates, the same as sorting the search by update date
+				self.get_search_manga_list(params, None, page, FilterProcessor::latest_filters())
+			}
+			id => {
+				if let Some(site_listing) = params.site_listings.iter().find(|l| l.id == id) {
+					return self.get_site_listing(params, site_listing, page);
+				}
+
+				if let Some(collection_id) = id.strip_prefix(COLLECTION_LISTING_PREFIX) {
+					// Members of a user collection
+					return Ok(
+						fetch_collection(&ctx, collection_id)?.into_manga_page_result(page, &ctx)
+					);
+				}
+
+				if let Some(slug_url) = id.strip_prefix(RELATIONS_LISTING_PREFIX) {
+					// Related titles of this site, opened from the details description
+					let entries = fetch_relations(&ctx, slug_url)?
+						.into_iter()
+						.filter(|relation| {
+							relation.media.site.is_none_or(|site| site == ctx.site_id)
+						})
+						.map(|relation| relation.media.into_manga(&ctx))
+						.collect();
+
+					return Ok(MangaPageResult {
+						entries,
+						has_next_page: false,
+					});
+				}
+
+				if let Some((kind, people_id)) = PeopleKind::from_listing_id(id) {
+					// Every title by an author, artist, publisher or team,
+					// for people links the titles of both roles together
+					let requests = kind
+						.query_keys()
+						.iter()
+						.map(|key| {
+							let people_params: Vec<(&str, &str)> = vec![
+								("page", page_str.as_str()),
+								("site_id[]", site_id_str.as_str()),
+								(*key, people_id),
+							];
+							Request::get(Url::manga_search_with_params(
+								&ctx.api_url,
+								&people_params,
+							))
+						})
+						.collect::<Result<Vec<Request>>>()?;
+
+					let mut result = MangaPageResult {
+						entries: Vec::new(),
+						has_next_page: false,
+					};
+					for response in send_all_authed(requests, &ctx) {
+						let found = response?
+							.get_json::<MangaListResponse>()?
+							.into_manga_page_result(&ctx);
+						result.has_next_page |= found.has_next_page;
+						for manga in found.entries {
+							if !result.entries.iter().any(|other| other.key == manga.key) {
+								result.entries.push(manga);
+							}
+						}
+					}
+					return Ok(result);
+				}
+
+				// Bookmark folders of the logged in user
+				let folder = BookmarkFolder::from_listing_id(id)
+					.ok_or_else(|| AidokuError::message("Unknown listing"))?;
+
+				if !is_logged_in() {
+					return Err(AidokuError::message("Log in to view bookmarks"));
+				}
+
+				let mut bookmark_params = folder.query_params();
+				bookmark_params.push(("page", page_str));
+				bookmark_params.push(("site_id[]", site_id_str));
+				bookmark_params.push(("sort_by", "last_chapter_at".into()));
+
+				let params_for_url: Vec<(&str, &str)> = bookmark_params
+					.iter()
+					.map(|(k, v)| (*k, v.as_str()))
+					.collect();
 
-				let latest_url = Url::manga_search_with_params(&ctx.api_url, &latest_params);
+				let bookmarks_url = Url::manga_search_with_params(&ctx.api_url, &params_for_url);
 
-				let response = Request::get(latest_url)?
+				Ok(Request::get(bookmarks_url)?
 					.authed(&ctx)?
-					.get_json::<MangaListResponse>()?;
-
-				let entries: Vec<Manga> = response
-					.data
-					.into_iter()
-					.map(|manga_lib_manga| manga_lib_manga.into_manga(&ctx))
-					.collect();
+					.get_json::<MangaListResponse>()?
+					.into_manga_page_result(&ctx))
+			}
+		}
+	}
 
-				let has_next_page = response.meta.has_next_page.unwrap_or_default();
+	fn get_dynamic_listings(&self, params: &Params) -> Result<Vec<Listing>> {
+		let mut listings: Vec<Listing> = params
+			.site_listings
+			.iter()
+			.map(|listing| Listing {
+				id: listing.id.into(),
+				name: listing.name.into(),
+				kind: ListingKind::Default,
+			})
+			.collect();
 
-				Ok(MangaPageResult {
-					entries,
-					has_next_page,
-				})
-			}
-			_ => Err(AidokuError::message("Unknown listing")),
+		if is_logged_in() {
+			listings.extend(bookmark_listings());
 		}
+
+		Ok(listings)
 	}
 
 	fn get_image_request(
@@ -273,12 +494,23 @@ pub trait Impl {
 		_context: Option<PageContext>,
 	) -> Result<Request> {
 		let ctx = Context::from_params(params);
-
-		Ok(Request::get(url)?
-			.header("Origin", &ctx.base_url)
-			.header("Referer", &ctx.api_url)
-			.header("Site-Id", &ctx.site_id.to_string())
-			.header("User-Agent", USER_AGENT))
+		let with_headers = |request: Request| {
+			request
+				.header("Origin", &ctx.base_url)
+				.header("Referer", &ctx.api_url)
+				.header("Site-Id", &ctx.site_id.to_string())
+				.header("User-Agent", USER_AGENT)
+		};
+
+		// Servers are checked now and then rather than for every image,
+		// anything but a successful answer counts as down
+		let url = get_image_url(&ctx, &url, |candidate| {
+			Request::head(candidate)
+				.and_then(|request| with_headers(request).send())
+				.is_ok_and(|response| (200..300).contains(&response.status_code()))
+		});
+
+		Ok(with_headers(Request::get(url)?))
 	}
 
 	fn get_alternate_covers(&self, params: &Params, manga: Manga) -> Result<Vec<String>> {
@@ -314,7 +546,7 @@ pub trait Impl {
 
 		let (chapter_number, volume_number) = (parts[0], parts[1]);
 
-		let chapters = get_chapters_cache(None).get_chapters(&manga_key, &ctx)?;
+		let chapters = get_chapters_cache().get_chapters(&manga_key, &ctx, None)?;
 
 		let chapter = chapters
 			.iter()
@@ -322,20 +554,94 @@ pub trait Impl {
 			.ok_or_else(|| AidokuError::message("Chapter not found"))?;
 
 		let branch_id = chapter
-			.branches
-			.first()
+			.preferred_branch(&get_branch_preference())
 			.map(|b| b.id)
 			.ok_or_else(|| AidokuError::message("No branch ID found"))?;
 
 		Ok(branch_id.to_string())
 	}
 
+	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
+		let ctx = Context::from_params(params);
+		let mut hosts = vec![ctx.base_url.as_str(), ctx.api_url.as_str()];
+		hosts.extend(params.domains.iter());
+
+		match parse_link(&url, &hosts) {
+			// ex: https://mangalib.me/ru/manga/7580--i-alone-level-up
+			Some(LibGroupLink::Manga { slug_url }) => {
+				Ok(Some(DeepLinkResult::Manga { key: slug_url }))
+			}
+			// ex: https://mangalib.me/ru/7580--i-alone-level-up/read/v1/c10?bid=123
+			Some(LibGroupLink::Chapter {
+				slug_url,
+				volume,
+				number,
+				branch_id,
+			}) => {
+				// Chapter keys are branch ids, so the chapter list is needed to find one
+				let key = get_chapters_cache()
+					.get_chapters(&slug_url, &ctx, Some(CHAPTERS_TTL))
+					.ok()
+					.and_then(|chapters| {
+						find_branch(
+							&chapters,
+							&volume,
+							&number,
+							branch_id,
+							&get_branch_preference(),
+						)
+						.map(|branch| branch.id.to_string())
+					});
+
+				Ok(Some(match key {
+					Some(key) => DeepLinkResult::Chapter {
+						manga_key: slug_url,
+						key,
+					},
+					None => DeepLinkResult::Manga { key: slug_url },
+				}))
+			}
+			// ex: https://mangalib.me/ru/people/4583--oda-eiichiro
+			Some(LibGroupLink::People { kind, id, slug }) => {
+				Ok(Some(DeepLinkResult::Listing(Listing {
+					id: format!("{}-{id}", kind.id()),
+					name: listing_name(kind, &slug),
+					kind: ListingKind::Default,
+				})))
+			}
+			// ex: https://mangalib.me/ru/collections/123
+			Some(LibGroupLink::Collection { id }) => {
+				let collection = fetch_collection(&ctx, &id)?.data;
+				let name = if collection.name.is_empty() {
+					"Коллекция".into()
+				} else {
+					collection.name
+				};
+
+				Ok(Some(DeepLinkResult::Listing(Listing {
+					id: format!("{COLLECTION_LISTING_PREFIX}{}", collection.id),
+					name,
+					kind: ListingKind::Default,
+				})))
+			}
+			// ex: https://mangalib.me/ru/manga/7580--i-alone-level-up?section=relations
+			Some(LibGroupLink::Relations { slug_url }) => {
+				Ok(Some(DeepLinkResult::Listing(Listing {
+					id: format!("{RELATIONS_LISTING_PREFIX}{slug_url}"),
+					name: "Связанное".into(),
+					kind: ListingKind::Default,
+				})))
+			}
+			None => Ok(None),
+		}
+	}
+
 	fn handle_notification(&self, params: &Params, notification: String) {
 		let ctx = Context::from_params(params);
 
 		match notification.as_str() {
 			"system.endMigration" => {
-				get_chapters_cache(None).clear();
+				get_chapters_cache().clear();
 			}
 			"token.changed" => {
 				clear_user_id();
@@ -345,3 +651,54 @@ pub trait Impl {
 		}
 	}
 }
+
+fn fetch_collection(ctx: &Context, collection_id: &str) -> Result<CollectionResponse> {
+	Request::get(Url::collection(&ctx.api_url, collection_id))?
+		.authed(ctx)?
+		.get_json::<CollectionResponse>()
+}
+
+fn fetch_relations(ctx: &Context, slug_url: &str) -> Result<Vec<LibGroupRelation>> {
+	Ok(Request::get(Url::manga_relations(&ctx.api_url, slug_url))?
+		.authed(ctx)?
+		.get_json::<RelationsResponse>()?
+		.data)
+}
+
+/// Catalog search URL for filters with people already resolved to ids
+pub fn search_url(
+	ctx: &Context,
+	default_age_ratings: &[&str],
+	mut query_params: Vec<(&'static str, String)>,
+	filters: Vec<FilterValue>,
+) -> String {
+	let filter_processor = FilterProcessor::new();
+	query_params.extend(filter_processor.process_filters(filters));
+
+	// Adult sites only show every age rating to logged-in users who ask for them
+	if is_logged_in() {
+		let defaults =
+			FilterProcessor::default_age_rating_params(default_age_ratings, &query_params);
+		query_params.extend(defaults);
+	}
+
+	let params_for_url: Vec<(&str, &str)> =
+		query_params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+	Url::manga_search_with_params(&ctx.api_url, &params_for_url)
+}
+
+/// Explain why a chapter came back without content
+fn chapter_lock_error(branch: Option<&LibGroupChapterBranch>, manga: &Manga) -> AidokuError {
+	let reason = branch
+		.and_then(LibGroupChapterBranch::lock_reason)
+		.or_else(|| {
+			(matches!(manga.content_rating, ContentRating::NSFW) && !is_logged_in())
+				.then_some(LockReason::AgeRestricted)
+		});
+
+	match reason {
+		Some(reason) => AidokuError::message(reason.message()),
+		None => AidokuError::message("Chapter is empty"),
+	}
+}
@@ -1,28 +1,50 @@
 #![no_std]
 use aidoku::{
-	AlternateCoverProvider, Chapter, FilterValue, Home, HomeLayout, ImageRequestProvider, Listing,
-	ListingProvider, Manga, MangaPageResult, MigrationHandler, NotificationHandler, Page,
-	PageContext, Result, Source,
+	AlternateCoverProvider, Chapter, DeepLinkHandler, DeepLinkResult, DynamicListings, FilterValue,
+	Home, HomeLayout, ImageRequestProvider, Listing, ListingProvider, Manga, MangaPageResult,
+	MigrationHandler, NotificationHandler, Page, PageContext, PageDescriptionProvider, Result,
+	Source,
 	alloc::{String, Vec, borrow::Cow},
 	imports::net::Request,
 };
 
 mod auth;
+mod bookmarks;
 mod cdn;
 mod chapters;
+mod comments;
 mod context;
 mod converters;
 mod endpoints;
 mod filters;
 mod home;
 mod imp;
+mod links;
 mod models;
+mod people;
 mod settings;
 
 pub use imp::Impl;
 
+/// Listing declared by a site crate, served from the catalog with fixed query parameters
+#[derive(Clone, Copy, Debug)]
+pub struct SiteListing {
+	pub id: &'static str,
+	pub name: &'static str,
+	/// Catalog query parameters, e.g. `("sort_by", "created_at")`
+	pub query: &'static [(&'static str, &'static str)],
+	/// Also show the listing as a scroller on the home page
+	pub on_home: bool,
+}
+
 pub struct Params {
 	pub site_id: Cow<'static, u8>,
+	/// `caution[]` ids requested for logged-in users when no age rating is chosen
+	pub default_age_ratings: Cow<'static, [&'static str]>,
+	/// Site specific listings and home sections, after the shared ones
+	pub site_listings: Cow<'static, [SiteListing]>,
+	/// Web domains links are accepted from besides the configured ones, e.g. `mangalib.me`
+	pub domains: Cow<'static, [&'static str]>,
 }
 
 pub struct LibGroup<T: Impl> {
@@ -67,6 +89,13 @@ impl<T: Impl> ListingProvider for LibGroup<T> {
 		self.inner.get_manga_list(&self.params, listing, page)
 	}
 }
+
+impl<T: Impl> DynamicListings for LibGroup<T> {
+	fn get_dynamic_listings(&self) -> Result<Vec<Listing>> {
+		self.inner.get_dynamic_listings(&self.params)
+	}
+}
+
 impl<T: Impl> Home for LibGroup<T> {
 	fn get_home(&self) -> Result<HomeLayout> {
 		self.inner.get_home(&self.params)
@@ -79,6 +108,12 @@ impl<T: Impl> ImageRequestProvider for LibGroup<T> {
 	}
 }
 
+impl<T: Impl> PageDescriptionProvider for LibGroup<T> {
+	fn get_page_description(&self, page: Page) -> Result<String> {
+		self.inner.get_page_description(&self.params, page)
+	}
+}
+
 impl<T: Impl> AlternateCoverProvider for LibGroup<T> {
 	fn get_alternate_covers(&self, manga: Manga) -> Result<Vec<String>> {
 		self.inner.get_alternate_covers(&self.params, manga)
@@ -96,6 +131,12 @@ impl<T: Impl> MigrationHandler for LibGroup<T> {
 	}
 }
 
+impl<T: Impl> DeepLinkHandler for LibGroup<T> {
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		self.inner.handle_deep_link(&self.params, url)
+	}
+}
+
 impl<T: Impl> NotificationHandler for LibGroup<T> {
 	fn handle_notification(&self, notification: String) {
 		self.inner.handle_notification(&self.params, notification);
@@ -0,0 +1,137 @@
+use aidoku::alloc::{String, Vec};
+
+use crate::models::chapter::{LibGroupChapterBranch, LibGroupChapterListItem};
+use crate::people::PeopleKind;
+use crate::settings::BranchPreference;
+
+/// Query of the title page section listing related titles
+pub const RELATIONS_SECTION_QUERY: &str = "section=relations";
+
+/// Page of the site a shared link points to
+#[derive(Clone, Debug, PartialEq)]
+pub enum LibGroupLink {
+	/// `/ru/manga/<slug>`, `/ru/book/<slug>` or `/api/manga/<slug>`
+	Manga { slug_url: String },
+	/// `/ru/manga/<slug>?section=relations`
+	Relations { slug_url: String },
+	/// `/ru/<slug>/read/v<volume>/c<number>?bid=<branch_id>`
+	Chapter {
+		slug_url: String,
+		volume: String,
+		number: String,
+		branch_id: Option<i32>,
+	},
+	/// `/ru/collections/<id>` or `/api/collections/<id>`
+	Collection { id: String },
+	/// `/ru/people/<id>--<slug>`, `/ru/team/<id>--<slug>` or `/ru/publisher/<id>--<slug>`
+	People {
+		kind: PeopleKind,
+		id: String,
+		/// `<id>--<slug>` as in the link, the slug is a transliterated name
+		slug: String,
+	},
+}
+
+/// Parse a web or API link on one of the given hosts
+pub fn parse_link(url: &str, hosts: &[&str]) -> Option<LibGroupLink> {
+	let rest = url
+		.strip_prefix("https://")
+		.or_else(|| url.strip_prefix("http://"))?;
+	let (host, rest) = rest.split_once('/').unwrap_or((rest, ""));
+	if !hosts
+		.iter()
+		.any(|h| normalize_host(h) == normalize_host(host))
+	{
+		return None;
+	}
+
+	let rest = rest.split('#').next().unwrap_or_default();
+	let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+	let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+	// The language prefix is optional in older links
+	let segments = match segments.split_first() {
+		Some((&"ru", rest)) => rest,
+		_ => &segments[..],
+	};
+
+	match segments {
+		["manga" | "book", slug, ..] if query.split('&').any(|p| p == RELATIONS_SECTION_QUERY) => {
+			Some(LibGroupLink::Relations {
+				slug_url: (*slug).into(),
+			})
+		}
+		["manga" | "book", slug, ..] | ["api", "manga", slug, ..] => Some(LibGroupLink::Manga {
+			slug_url: (*slug).into(),
+		}),
+		["collections", id] | ["api", "collections", id] => {
+			Some(LibGroupLink::Collection { id: (*id).into() })
+		}
+		[page @ ("people" | "team" | "publisher"), slug, ..] => {
+			let kind = match *page {
+				"people" => PeopleKind::Person,
+				"team" => PeopleKind::Team,
+				_ => PeopleKind::Publisher,
+			};
+			let id = slug.split("--").next().unwrap_or_default();
+			(!id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())).then(|| {
+				LibGroupLink::People {
+					kind,
+					id: id.into(),
+					slug: (*slug).into(),
+				}
+			})
+		}
+		[slug, "read", volume, number, ..] => Some(LibGroupLink::Chapter {
+			slug_url: (*slug).into(),
+			volume: volume.strip_prefix('v')?.into(),
+			number: number.strip_prefix('c')?.into(),
+			branch_id: query
+				.split('&')
+				.find_map(|pair| pair.strip_prefix("bid="))
+				.and_then(|id| id.parse().ok()),
+		}),
+		_ => None,
+	}
+}
+
+/// Find the branch a reader link opens: the one with its `bid`, otherwise the preferred one
+pub fn find_branch<'a>(
+	chapters: &'a [LibGroupChapterListItem],
+	volume: &str,
+	number: &str,
+	branch_id: Option<i32>,
+	preference: &BranchPreference,
+) -> Option<&'a LibGroupChapterBranch> {
+	let item = chapters
+		.iter()
+		.find(|item| same_number(&item.volume, volume) && same_number(&item.number, number))?;
+
+	branch_id
+		.and_then(|id| {
+			item.branches
+				.iter()
+				.find(|branch| branch.branch_id == Some(id))
+		})
+		.or_else(|| item.preferred_branch(preference))
+}
+
+/// Host without scheme, `www.` or trailing slash
+fn normalize_host(host: &str) -> &str {
+	let host = host
+		.strip_prefix("https://")
+		.or_else(|| host.strip_prefix("http://"))
+		.unwrap_or(host);
+	let host = host.split('/').next().unwrap_or_default();
+	host.strip_prefix("www.").unwrap_or(host)
+}
+
+/// Reader links print numbers as floats, so `c10` must match chapter `10.0` and the like
+fn same_number(a: &str, b: &str) -> bool {
+	match (a.parse::<f32>(), b.parse::<f32>()) {
+		(Ok(a), Ok(b)) => a == b,
+		_ => a == b,
+	}
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,180 @@
+use super::*;
+use crate::models::common::LibGroupRestrictedView;
+use aidoku::alloc::{string::ToString, vec};
+use aidoku_test::aidoku_test;
+
+const HOSTS: &[&str] = &[
+	"https://mangalib.me",
+	"https://api.lib.social",
+	"slashlib.me",
+];
+
+#[aidoku_test]
+fn manga_links() {
+	let expected = Some(LibGroupLink::Manga {
+		slug_url: "7580--i-alone-level-up".to_string(),
+	});
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/manga/7580--i-alone-level-up", HOSTS),
+		expected
+	);
+	assert_eq!(
+		parse_link(
+			"https://www.mangalib.me/ru/manga/7580--i-alone-level-up?section=chapters",
+			HOSTS
+		),
+		expected
+	);
+	assert_eq!(
+		parse_link(
+			"https://api.lib.social/api/manga/7580--i-alone-level-up",
+			HOSTS
+		),
+		expected
+	);
+	assert_eq!(
+		parse_link("https://slashlib.me/ru/book/7580--i-alone-level-up/", HOSTS),
+		expected
+	);
+}
+
+#[aidoku_test]
+fn relations_links() {
+	assert_eq!(
+		parse_link(
+			"https://mangalib.me/ru/manga/7580--i-alone-level-up?section=relations",
+			HOSTS
+		),
+		Some(LibGroupLink::Relations {
+			slug_url: "7580--i-alone-level-up".to_string(),
+		})
+	);
+}
+
+#[aidoku_test]
+fn reader_links() {
+	assert_eq!(
+		parse_link(
+			"https://mangalib.me/ru/7580--i-alone-level-up/read/v1/c10?bid=123&ui=5",
+			HOSTS
+		),
+		Some(LibGroupLink::Chapter {
+			slug_url: "7580--i-alone-level-up".to_string(),
+			volume: "1".to_string(),
+			number: "10".to_string(),
+			branch_id: Some(123),
+		})
+	);
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/slug/read/v2/c10.5", HOSTS),
+		Some(LibGroupLink::Chapter {
+			slug_url: "slug".to_string(),
+			volume: "2".to_string(),
+			number: "10.5".to_string(),
+			branch_id: None,
+		})
+	);
+}
+
+#[aidoku_test]
+fn collection_links() {
+	let expected = Some(LibGroupLink::Collection {
+		id: "123".to_string(),
+	});
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/collections/123", HOSTS),
+		expected
+	);
+	assert_eq!(
+		parse_link(
+			"https://api.lib.social/api/collections/123?fields[]=items",
+			HOSTS
+		),
+		expected
+	);
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/collections", HOSTS),
+		None
+	);
+}
+
+#[aidoku_test]
+fn people_links() {
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/people/4583--oda-eiichiro", HOSTS),
+		Some(LibGroupLink::People {
+			kind: PeopleKind::Person,
+			id: "4583".to_string(),
+			slug: "4583--oda-eiichiro".to_string(),
+		})
+	);
+	assert_eq!(
+		parse_link(
+			"https://mangalib.me/ru/team/12--some-team?section=titles",
+			HOSTS
+		),
+		Some(LibGroupLink::People {
+			kind: PeopleKind::Team,
+			id: "12".to_string(),
+			slug: "12--some-team".to_string(),
+		})
+	);
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/publisher/7", HOSTS),
+		Some(LibGroupLink::People {
+			kind: PeopleKind::Publisher,
+			id: "7".to_string(),
+			slug: "7".to_string(),
+		})
+	);
+	assert_eq!(parse_link("https://mangalib.me/ru/people/oda", HOSTS), None);
+}
+
+#[aidoku_test]
+fn foreign_and_unknown_links() {
+	assert_eq!(parse_link("https://example.com/ru/manga/slug", HOSTS), None);
+	assert_eq!(parse_link("https://mangalib.me/ru/catalog", HOSTS), None);
+	assert_eq!(
+		parse_link("https://mangalib.me/ru/slug/read/1/10", HOSTS),
+		None
+	);
+	assert_eq!(parse_link("mangalib.me/ru/manga/slug", HOSTS), None);
+}
+
+fn make_branch(id: i32, branch_id: Option<i32>) -> LibGroupChapterBranch {
+	LibGroupChapterBranch {
+		id,
+		branch_id,
+		restricted_view: Some(LibGroupRestrictedView {
+			is_open: true,
+			..Default::default()
+		}),
+		..Default::default()
+	}
+}
+
+#[aidoku_test]
+fn reader_link_picks_branch() {
+	let chapters = vec![
+		LibGroupChapterListItem {
+			volume: "1".to_string(),
+			number: "9".to_string(),
+			..Default::default()
+		},
+		LibGroupChapterListItem {
+			volume: "1".to_string(),
+			number: "10.0".to_string(),
+			branches: vec![make_branch(100, Some(1)), make_branch(200, Some(2))],
+			..Default::default()
+		},
+	];
+
+	let by_bid = find_branch(&chapters, "1", "10", Some(2), &BranchPreference::All);
+	assert_eq!(by_bid.map(|b| b.id), Some(200));
+
+	// Unknown or missing bid falls back to the preferred branch
+	let fallback = find_branch(&chapters, "1", "10", Some(3), &BranchPreference::All);
+	assert_eq!(fallback.map(|b| b.id), Some(100));
+
+	assert!(find_branch(&chapters, "2", "10", None, &BranchPreference::All).is_none());
+}
@@ -3,16 +3,20 @@ use aidoku::{
 	alloc::{String, Vec, string::ToString, vec},
 	prelude::*,
 };
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 
 use crate::{
 	cdn::get_selected_image_server_url,
 	context::Context,
-	converters::{convert_html_to_markdown, convert_model_to_markdown},
+	converters::{
+		ContentPart, MAX_TEXT_PAGE_LEN, convert_html_to_markdown, convert_model_to_parts,
+		split_text_pages,
+	},
 	endpoints::Url,
 	models::common::LibGroupModerated,
+	settings::BranchPreference,
 };
 
 use super::common::{LibGroupRestrictedView, LibGroupTeam};
@@ -29,6 +33,39 @@ pub struct LibGroupChapterBranch {
 	pub moderation: Option<LibGroupModerated>,
 }
 
+/// Why a chapter can't be read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockReason {
+	/// Paid or early access chapter, free from the given timestamp if known
+	Paid(Option<i64>),
+	/// Chapter awaiting moderation
+	Moderation,
+	/// Adult title hidden from anonymous users
+	AgeRestricted,
+}
+
+impl LockReason {
+	pub fn message(self) -> String {
+		match self {
+			LockReason::Paid(Some(free_from)) => {
+				let date = DateTime::<Utc>::from_timestamp(free_from, 0)
+					.map(|date| date.format("%d.%m.%Y").to_string())
+					.unwrap_or_default();
+				format!(
+					"This chapter is in paid early access until {date}. Buy it on the website or wait until it becomes free"
+				)
+			}
+			LockReason::Paid(None) => {
+				String::from("This chapter is paid. Buy it on the website to read it here")
+			}
+			LockReason::Moderation => String::from("This chapter is awaiting moderation"),
+			LockReason::AgeRestricted => String::from(
+				"This title is age-restricted. Log in with an account whose age is confirmed on the website to read it",
+			),
+		}
+	}
+}
+
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupChapterBranchUser {
@@ -77,12 +114,18 @@ pub struct LibGroupPage {
 #[derive(Deserialize, Debug, Clone)]
 pub struct LibGroupImageChapter {
 	pub pages: Vec<LibGroupPage>,
+	/// Translator note left by the team
+	#[serde(default)]
+	pub note: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct LibGroupTextChapter {
 	pub content: LibGroupContentType,
 	pub attachments: Vec<LibGroupAttachment>,
+	/// Translator note left by the team
+	#[serde(default)]
+	pub note: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -97,18 +140,14 @@ pub struct LibGroupContentModel {
 	pub content: Option<Vec<LibGroupContentNode>>,
 }
 
+/// ProseMirror-style node: block nodes have `content`, text nodes have `text` and `marks`
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupContentNode {
 	#[serde(rename = "type")]
 	pub node_type: String,
-	pub content: Option<Vec<LibGroupTextNode>>,
+	pub content: Option<Vec<LibGroupContentNode>>,
 	pub attrs: Option<LibGroupNodeAttrs>,
-}
-
-#[derive(Default, Deserialize, Debug, Clone)]
-#[serde(default)]
-pub struct LibGroupTextNode {
 	pub text: Option<String>,
 	pub marks: Option<Vec<LibGroupMark>>,
 }
@@ -118,12 +157,22 @@ pub struct LibGroupTextNode {
 pub struct LibGroupMark {
 	#[serde(rename = "type")]
 	pub mark_type: String,
+	pub attrs: Option<LibGroupMarkAttrs>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupMarkAttrs {
+	pub href: Option<String>,
 }
 
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupNodeAttrs {
 	pub images: Option<Vec<LibGroupImageAttr>>,
+	pub level: Option<u8>,
+	/// Footnote text
+	pub text: Option<String>,
 }
 
 #[derive(Default, Deserialize, Debug, Clone)]
@@ -173,44 +222,131 @@ impl<'de> Deserialize<'de> for LibGroupContentType {
 	}
 }
 
+impl LibGroupContentType {
+	/// Render as a single markdown string, keeping images inline
+	pub fn to_markdown(&self, image_base: &str) -> String {
+		match self {
+			LibGroupContentType::Html(html) => convert_html_to_markdown(html),
+			LibGroupContentType::Model(model) => convert_model_to_parts(model, &[], image_base)
+				.into_iter()
+				.map(|part| match part {
+					ContentPart::Text(text) => text,
+					ContentPart::Image(url) => format!("![]({url})"),
+				})
+				.collect::<Vec<_>>()
+				.join("\n\n"),
+		}
+	}
+}
+
+impl LibGroupChapterBranch {
+	fn timestamp(&self) -> Option<i64> {
+		DateTime::parse_from_rfc3339(&self.created_at)
+			.ok()
+			.map(|d| d.timestamp())
+	}
+
+	/// Why the branch can't be read, if it can't
+	pub fn lock_reason(&self) -> Option<LockReason> {
+		if let Some(restricted_view) = &self.restricted_view
+			&& !restricted_view.is_accessible()
+		{
+			let free_from = restricted_view
+				.end_date
+				.as_deref()
+				.and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+				.map(|date| date.timestamp());
+			return Some(LockReason::Paid(free_from));
+		}
+
+		if self
+			.moderation
+			.as_ref()
+			.is_some_and(|m| m.label == "На модерации")
+		{
+			return Some(LockReason::Moderation);
+		}
+
+		None
+	}
+
+	fn is_locked(&self) -> bool {
+		self.lock_reason().is_some()
+	}
+
+	/// Check if the branch is translated by a team with the given name or id
+	fn matches_team(&self, team: &str) -> bool {
+		if team.is_empty() {
+			return false;
+		}
+		let team = team.to_lowercase();
+		self.teams
+			.iter()
+			.any(|t| t.id.to_string() == team || t.name.to_lowercase() == team)
+	}
+}
+
 impl LibGroupChapterListItem {
+	/// Pick the single branch that best matches the preference
+	pub fn preferred_branch(
+		&self,
+		preference: &BranchPreference,
+	) -> Option<&LibGroupChapterBranch> {
+		match preference {
+			BranchPreference::All => self.branches.first(),
+			BranchPreference::Pinned(team) => self
+				.branches
+				.iter()
+				.find(|branch| branch.matches_team(team))
+				.or_else(|| self.newest_branch()),
+			BranchPreference::Newest => self.newest_branch(),
+		}
+	}
+
+	/// Newest branch, preferring ones that can be read
+	fn newest_branch(&self) -> Option<&LibGroupChapterBranch> {
+		self.branches
+			.iter()
+			.max_by_key(|branch| (!branch.is_locked(), branch.timestamp().unwrap_or_default()))
+	}
+
+	/// Drop every branch except the preferred one, unless all branches should be shown
+	pub fn retain_preferred_branches(&mut self, preference: &BranchPreference) {
+		if *preference == BranchPreference::All {
+			return;
+		}
+		if let Some(id) = self.preferred_branch(preference).map(|branch| branch.id) {
+			self.branches.retain(|branch| branch.id == id);
+		}
+	}
+
 	pub fn into_chapters(
-		self,
+		mut self,
 		base_url: &str,
 		slug_url: &str,
 		user_id: &Option<i32>,
+		preference: &BranchPreference,
 	) -> Vec<Chapter> {
+		self.retain_preferred_branches(preference);
+
+		let chapter_number = self.number.parse::<f32>().ok();
+		let volume_number = self.volume.parse::<f32>().ok();
+
 		self.branches
 			.into_iter()
 			.map(|branch| {
-				let chapter_number = self.number.parse::<f32>().ok();
-				let volume_number = self.volume.parse::<f32>().ok();
-
 				let scanlators: Vec<String> = if branch.teams.is_empty() {
 					vec![branch.user.username.clone()]
 				} else {
 					branch.teams.iter().map(|team| team.name.clone()).collect()
 				};
 
-				let locked = branch
-					.restricted_view
-					.as_ref()
-					.map(|rv| !rv.is_open)
-					.unwrap_or(false)
-					|| branch
-						.moderation
-						.as_ref()
-						.map(|m| m.label == "На модерации")
-						.unwrap_or(false);
-
 				Chapter {
 					key: branch.id.to_string(),
 					title: self.name.clone(),
 					chapter_number,
 					volume_number,
-					date_uploaded: DateTime::parse_from_rfc3339(&branch.created_at)
-						.ok()
-						.map(|d| d.timestamp()),
+					date_uploaded: branch.timestamp(),
 					scanlators: Some(scanlators),
 					url: Some(Url::chapter_page(
 						base_url,
@@ -220,7 +356,7 @@ impl LibGroupChapterListItem {
 						branch.branch_id,
 						user_id,
 					)),
-					locked,
+					locked: branch.is_locked(),
 					..Default::default()
 				}
 			})
@@ -232,16 +368,41 @@ impl LibGroupChapterListItem {
 		base_url: &str,
 		slug_url: &str,
 		user_id: &Option<i32>,
+		preference: &BranchPreference,
 	) -> Vec<Chapter> {
 		items
 			.into_iter()
 			.rev()
-			.flat_map(|item| item.into_chapters(base_url, slug_url, user_id))
+			.flat_map(|item| item.into_chapters(base_url, slug_url, user_id, preference))
 			.collect()
 	}
 }
 
 impl LibGroupChapterData {
+	/// A locked chapter comes back without pages or text
+	pub fn is_empty(&self) -> bool {
+		match self {
+			LibGroupChapterData::Image(chapter) => chapter.pages.is_empty(),
+			LibGroupChapterData::Text(chapter) => match &chapter.content {
+				LibGroupContentType::Html(html) => html.trim().is_empty(),
+				LibGroupContentType::Model(model) => model
+					.content
+					.as_ref()
+					.is_none_or(|content| content.is_empty()),
+			},
+		}
+	}
+
+	/// Non-empty team note of the chapter
+	pub fn note(&self) -> Option<&str> {
+		match self {
+			LibGroupChapterData::Image(chapter) => chapter.note.as_deref(),
+			LibGroupChapterData::Text(chapter) => chapter.note.as_deref(),
+		}
+		.map(str::trim)
+		.filter(|note| !note.is_empty())
+	}
+
 	pub fn into_pages(self, ctx: &Context) -> Vec<Page> {
 		match self {
 			LibGroupChapterData::Image(chapter) => chapter.into_pages(ctx),
@@ -268,16 +429,31 @@ impl LibGroupImageChapter {
 
 impl LibGroupTextChapter {
 	pub fn into_pages(self, ctx: &Context) -> Vec<Page> {
-		let markdown = match self.content {
-			LibGroupContentType::Html(html) => convert_html_to_markdown(&html),
+		let image_base = get_selected_image_server_url(ctx);
+		let parts = match self.content {
+			LibGroupContentType::Html(html) => {
+				vec![ContentPart::Text(convert_html_to_markdown(&html))]
+			}
 			LibGroupContentType::Model(model) => {
-				convert_model_to_markdown(&model, &self.attachments, ctx)
+				convert_model_to_parts(&model, &self.attachments, &image_base)
 			}
 		};
 
-		vec![Page {
-			content: PageContent::text(markdown),
-			..Default::default()
-		}]
+		parts
+			.into_iter()
+			.flat_map(|part| match part {
+				ContentPart::Text(markdown) => split_text_pages(&markdown, MAX_TEXT_PAGE_LEN)
+					.into_iter()
+					.map(|text| Page {
+						content: PageContent::text(text),
+						..Default::default()
+					})
+					.collect::<Vec<_>>(),
+				ContentPart::Image(url) => vec![Page {
+					content: PageContent::url(url),
+					..Default::default()
+				}],
+			})
+			.collect()
 	}
 }
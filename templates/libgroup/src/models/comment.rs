@@ -0,0 +1,26 @@
+use aidoku::alloc::String;
+use serde::Deserialize;
+
+use super::chapter::LibGroupContentType;
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupComment {
+	pub id: i32,
+	pub comment: LibGroupContentType,
+	pub user: LibGroupCommentUser,
+	pub votes: LibGroupCommentVotes,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupCommentUser {
+	pub username: String,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupCommentVotes {
+	pub up: i32,
+	pub down: i32,
+}
@@ -55,6 +55,7 @@ pub struct LibGroupMeta {
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupTeam {
+	pub id: i32,
 	pub name: String,
 }
 
@@ -62,6 +63,19 @@ pub struct LibGroupTeam {
 #[serde(default)]
 pub struct LibGroupRestrictedView {
 	pub is_open: bool,
+	/// End of the early access period, after which the chapter is free
+	pub end_date: Option<String>,
+	/// The logged-in user bought the chapter
+	pub is_purchased: bool,
+	/// The logged-in user's subscription covers the chapter
+	pub is_subscribed: bool,
+}
+
+impl LibGroupRestrictedView {
+	/// Whether the chapter can be read, either for free or thanks to a purchase
+	pub fn is_accessible(&self) -> bool {
+		self.is_open || self.is_purchased || self.is_subscribed
+	}
 }
 
 impl LibGroupCover {
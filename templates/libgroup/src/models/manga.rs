@@ -1,11 +1,14 @@
 use aidoku::{
 	ContentRating, Manga, MangaStatus, UpdateStrategy, Viewer,
-	alloc::{String, Vec},
+	alloc::{String, Vec, string::ToString},
 	prelude::*,
 };
 use serde::Deserialize;
 
-use crate::{context::Context, endpoints::Url, models::common::LibGroupRating};
+use crate::{
+	bookmarks::BookmarkFolder, context::Context, endpoints::Url, links::RELATIONS_SECTION_QUERY,
+	models::common::LibGroupRating,
+};
 
 use super::common::{
 	LibGroupAgeRestriction, LibGroupCover, LibGroupMediaType, LibGroupStatus, LibGroupTag,
@@ -30,15 +33,86 @@ pub struct LibGroupManga {
 	pub authors: Option<Vec<LibGroupAuthor>>,
 	pub artists: Option<Vec<LibGroupAuthor>>,
 	pub status: LibGroupStatus,
+	pub site: Option<u8>,
+	pub views: Option<LibGroupViews>,
+	/// Related titles, fetched separately from `/relations`
+	#[serde(skip)]
+	pub relations: Option<Vec<LibGroupRelation>>,
+	/// Bookmark and like counters, fetched separately from `/stats`
+	#[serde(skip)]
+	pub stats: Option<LibGroupMangaStats>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupViews {
+	pub total: i64,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupMangaStats {
+	pub bookmarks: Option<LibGroupBookmarkStats>,
+	pub likes: Option<LibGroupCount>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupBookmarkStats {
+	pub count: i64,
+	pub stats: Vec<LibGroupBookmarkStat>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupBookmarkStat {
+	/// Bookmark folder status
+	pub value: u8,
+	pub label: String,
+	pub count: i64,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupCount {
+	pub count: i64,
 }
 
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupAuthor {
+	pub id: i32,
 	pub name: String,
 	pub rus_name: Option<String>,
 }
 
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupRelation {
+	pub relation: LibGroupRelationType,
+	pub media: LibGroupManga,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupRelationType {
+	pub label: String,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupCollection {
+	pub id: i32,
+	pub name: String,
+	pub items: Vec<LibGroupCollectionItem>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LibGroupCollectionItem {
+	pub media: LibGroupManga,
+}
+
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LibGroupCoverItem {
@@ -49,11 +123,7 @@ impl LibGroupManga {
 	pub fn into_manga(self, ctx: &Context) -> Manga {
 		Manga {
 			key: self.slug_url.clone(),
-			title: if !self.rus_name.is_empty() {
-				self.rus_name.clone()
-			} else {
-				self.eng_name.clone().unwrap_or_default()
-			},
+			title: self.display_title(),
 			cover: Some(self.cover.get_cover_url(&ctx.cover_quality)),
 			artists: self.artists.as_ref().map(|artists| {
 				artists
@@ -77,8 +147,8 @@ impl LibGroupManga {
 					})
 					.collect()
 			}),
-			description: Some(Self::detailed_description(&self)),
-			url: Some(Url::manga_page(&ctx.base_url, &self.slug_url)),
+			description: Some(Self::detailed_description(&self, ctx)),
+			url: Some(Url::title_page(ctx.site_id, &ctx.base_url, &self.slug_url)),
 			tags: self
 				.tags
 				.as_ref()
@@ -113,9 +183,15 @@ impl LibGroupManga {
 		}
 	}
 
-	fn detailed_description(maga: &LibGroupManga) -> String {
+	fn detailed_description(maga: &LibGroupManga, ctx: &Context) -> String {
 		let mut description = String::new();
 
+		// Rating and counters
+		if let Some(header) = maga.stats_header() {
+			description.push_str(&header);
+			description.push_str("\n\n");
+		}
+
 		// Summary
 		if let Some(summary) = &maga.summary
 			&& !summary.is_empty()
@@ -124,37 +200,6 @@ impl LibGroupManga {
 			description.push_str("\n\n");
 		}
 
-		// Rating section
-		if let Some(rating) = &maga.rating
-			&& let Ok(avg) = rating.average.parse::<f32>()
-		{
-			let stars_count = (avg / 2.0).clamp(0.0, 5.0);
-
-			let full_stars = stars_count as u8;
-			let half_star = ((stars_count * 10.0) as u8 % 10) >= 5;
-			let empty_stars = 5 - full_stars - if half_star { 1 } else { 0 };
-
-			let full_symbol = "★";
-			let half_symbol = "✮";
-			let empty_symbol = "☆";
-
-			let mut stars_str = String::new();
-			for _ in 0..full_stars {
-				stars_str.push_str(full_symbol);
-			}
-			if half_star {
-				stars_str.push_str(half_symbol);
-			}
-			for _ in 0..empty_stars {
-				stars_str.push_str(empty_symbol);
-			}
-
-			description.push_str(&format!(
-				"{} {:.2} (голосов: {})\n\n",
-				stars_str, avg, rating.votes
-			));
-		}
-
 		// Alternative names
 		let mut alt_names: Vec<String> = Vec::new();
 
@@ -175,6 +220,160 @@ impl LibGroupManga {
 			description.push_str(&alt_names.join(", "));
 		}
 
+		// Related titles, including ones from other LibGroup sites
+		if let Some(relations) = &maga.relations
+			&& !relations.is_empty()
+		{
+			let related: Vec<String> = relations
+				.iter()
+				.map(|relation| {
+					let title = relation.media.display_title();
+					let (site_id, site_base) = match relation.media.site {
+						Some(site_id) if site_id != ctx.site_id => (
+							site_id,
+							Url::site_base(site_id).unwrap_or(ctx.base_url.as_str()),
+						),
+						_ => (ctx.site_id, ctx.base_url.as_str()),
+					};
+					let url = Url::title_page(site_id, site_base, &relation.media.slug_url);
+
+					if relation.relation.label.is_empty() {
+						format!("[{title}]({url})")
+					} else {
+						format!("{}: [{title}]({url})", relation.relation.label)
+					}
+				})
+				.collect();
+
+			if !description.is_empty() && !description.ends_with("\n\n") {
+				description.push_str("\n\n");
+			}
+			// The heading opens every related title of this site as a listing
+			let relations_url = format!(
+				"{}?{RELATIONS_SECTION_QUERY}",
+				Url::title_page(ctx.site_id, &ctx.base_url, &maga.slug_url)
+			);
+			description.push_str(&format!("[Связанное]({relations_url}):<br/>"));
+			description.push_str(&related.join("<br/>"));
+		}
+
 		description
 	}
+
+	/// Rating stars, then views, likes and bookmarks, then bookmarks per folder
+	fn stats_header(&self) -> Option<String> {
+		let mut lines: Vec<String> = Vec::new();
+
+		if let Some(rating) = &self.rating
+			&& let Ok(avg) = rating.average.parse::<f32>()
+		{
+			lines.push(format!(
+				"{} {:.2} (голосов: {})",
+				rating_stars(avg),
+				avg,
+				format_count(rating.votes.into())
+			));
+		}
+
+		let bookmarks = self
+			.stats
+			.as_ref()
+			.and_then(|stats| stats.bookmarks.as_ref());
+		let mut counters: Vec<String> = Vec::new();
+		if let Some(views) = &self.views
+			&& views.total > 0
+		{
+			counters.push(format!("Просмотры: {}", format_count(views.total)));
+		}
+		if let Some(likes) = self.stats.as_ref().and_then(|stats| stats.likes.as_ref())
+			&& likes.count > 0
+		{
+			counters.push(format!("Лайки: {}", format_count(likes.count)));
+		}
+		if let Some(bookmarks) = bookmarks
+			&& bookmarks.count > 0
+		{
+			counters.push(format!("В закладках: {}", format_count(bookmarks.count)));
+		}
+		if !counters.is_empty() {
+			lines.push(counters.join(" · "));
+		}
+
+		if let Some(bookmarks) = bookmarks {
+			let folders: Vec<String> = BookmarkFolder::ALL
+				.into_iter()
+				.filter_map(|folder| {
+					let stat = bookmarks
+						.stats
+						.iter()
+						.find(|stat| stat.value == folder.status())
+						.filter(|stat| stat.count > 0)?;
+					let label = if stat.label.is_empty() {
+						folder.name()
+					} else {
+						stat.label.as_str()
+					};
+					Some(format!("{label}: {}", format_count(stat.count)))
+				})
+				.collect();
+			if !folders.is_empty() {
+				lines.push(folders.join(" · "));
+			}
+		}
+
+		if lines.is_empty() {
+			None
+		} else {
+			Some(lines.join("<br/>"))
+		}
+	}
+
+	fn display_title(&self) -> String {
+		if !self.rus_name.is_empty() {
+			self.rus_name.clone()
+		} else {
+			self.eng_name.clone().unwrap_or_default()
+		}
+	}
+}
+
+/// Five stars for a 0-10 rating, with a half star when the remainder is at least .5
+fn rating_stars(avg: f32) -> String {
+	let stars_count = (avg / 2.0).clamp(0.0, 5.0);
+
+	let full_stars = stars_count as u8;
+	let half_star = ((stars_count * 10.0) as u8 % 10) >= 5;
+	let empty_stars = 5 - full_stars - if half_star { 1 } else { 0 };
+
+	let full_symbol = "★";
+	let half_symbol = "✮";
+	let empty_symbol = "☆";
+
+	let mut stars_str = String::new();
+	for _ in 0..full_stars {
+		stars_str.push_str(full_symbol);
+	}
+	if half_star {
+		stars_str.push_str(half_symbol);
+	}
+	for _ in 0..empty_stars {
+		stars_str.push_str(empty_symbol);
+	}
+	stars_str
+}
+
+/// Group thousands with spaces, as the site does: 1234567 -> "1 234 567"
+fn format_count(count: i64) -> String {
+	let digits = count.unsigned_abs().to_string();
+	let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+	if count < 0 {
+		grouped.push('-');
+	}
+	for (i, digit) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i) % 3 == 0 {
+			grouped.push(' ');
+		}
+		grouped.push(digit);
+	}
+	grouped
 }
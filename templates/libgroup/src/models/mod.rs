@@ -1,6 +1,11 @@
 pub mod chapter;
+pub mod comment;
 pub mod common;
 pub mod constants;
 pub mod manga;
+pub mod requests;
 pub mod responses;
 pub mod user;
+
+#[cfg(test)]
+mod test;
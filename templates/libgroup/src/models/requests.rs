@@ -0,0 +1,11 @@
+use aidoku::alloc::String;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReadingProgressRequest {
+	pub user_id: i32,
+	pub media_slug: String,
+	pub chapter_id: i32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub branch_id: Option<i32>,
+}
@@ -1,12 +1,21 @@
-use aidoku::alloc::{String, Vec};
+use aidoku::{
+	Manga, MangaPageResult,
+	alloc::{String, Vec},
+};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{common::LibGroupMeta, manga::LibGroupCoverItem, user::LibGroupUser};
+use crate::{
+	context::Context,
+	models::{common::LibGroupMeta, manga::LibGroupCoverItem, user::LibGroupUser},
+};
 
 use super::{
 	chapter::{LibGroupChapterData, LibGroupChapterListItem},
+	comment::LibGroupComment,
 	constants::LibGroupConstantsData,
-	manga::LibGroupManga,
+	manga::{
+		LibGroupAuthor, LibGroupCollection, LibGroupManga, LibGroupMangaStats, LibGroupRelation,
+	},
 };
 
 #[derive(Default, Deserialize, Debug, Clone)]
@@ -22,12 +31,74 @@ pub struct MangaListResponse {
 	pub meta: LibGroupMeta,
 }
 
+impl MangaListResponse {
+	pub fn into_manga_page_result(self, ctx: &Context) -> MangaPageResult {
+		let entries: Vec<Manga> = self
+			.data
+			.into_iter()
+			.map(|manga_lib_manga| manga_lib_manga.into_manga(ctx))
+			.collect();
+
+		MangaPageResult {
+			entries,
+			has_next_page: self.meta.has_next_page.unwrap_or_default(),
+		}
+	}
+}
+
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct MangaDetailResponse {
 	pub data: LibGroupManga,
 }
 
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MangaStatsResponse {
+	pub data: LibGroupMangaStats,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RelationsResponse {
+	pub data: Vec<LibGroupRelation>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CollectionResponse {
+	pub data: LibGroupCollection,
+}
+
+/// Collections come in one response, so their pages are cut locally
+const COLLECTION_PAGE_SIZE: usize = 30;
+
+impl CollectionResponse {
+	pub fn into_manga_page_result(self, page: i32, ctx: &Context) -> MangaPageResult {
+		let start = (page.max(1) as usize - 1) * COLLECTION_PAGE_SIZE;
+		let items = self.data.items;
+		let has_next_page = items.len() > start + COLLECTION_PAGE_SIZE;
+
+		let entries: Vec<Manga> = items
+			.into_iter()
+			.skip(start)
+			.take(COLLECTION_PAGE_SIZE)
+			.map(|item| item.media.into_manga(ctx))
+			.collect();
+
+		MangaPageResult {
+			entries,
+			has_next_page,
+		}
+	}
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PeopleSearchResponse {
+	pub data: Vec<LibGroupAuthor>,
+}
+
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct ChaptersResponse {
@@ -40,6 +111,12 @@ pub struct ChapterResponse {
 	pub data: Option<LibGroupChapterData>,
 }
 
+#[derive(Default, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CommentsResponse {
+	pub data: Vec<LibGroupComment>,
+}
+
 #[derive(Default, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct MangaCoversResponse {
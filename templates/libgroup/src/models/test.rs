@@ -0,0 +1,174 @@
+use crate::context::Context;
+use crate::models::{
+	common::LibGroupRating,
+	manga::{
+		LibGroupBookmarkStat, LibGroupBookmarkStats, LibGroupCount, LibGroupManga,
+		LibGroupMangaStats, LibGroupViews,
+	},
+	responses::{CollectionResponse, RelationsResponse},
+};
+use aidoku::{
+	alloc::{String, Vec, string::ToString, vec},
+	prelude::*,
+};
+use aidoku_test::aidoku_test;
+
+fn test_context() -> Context {
+	Context {
+		api_url: "http://fake.api".to_string(),
+		base_url: "https://mangalib.me".to_string(),
+		site_id: 1,
+		cover_quality: "default".to_string(),
+	}
+}
+
+fn description(manga: LibGroupManga) -> String {
+	manga
+		.into_manga(&test_context())
+		.description
+		.unwrap_or_default()
+}
+
+fn bookmark(value: u8, label: &str, count: i64) -> LibGroupBookmarkStat {
+	LibGroupBookmarkStat {
+		value,
+		label: label.to_string(),
+		count,
+	}
+}
+
+#[aidoku_test]
+fn stats_header_precedes_summary() {
+	let manga = LibGroupManga {
+		rus_name: "Ван-Пис".to_string(),
+		summary: Some("Пираты.".to_string()),
+		rating: Some(LibGroupRating {
+			average: "9.46".to_string(),
+			votes: 123456,
+		}),
+		views: Some(LibGroupViews { total: 98765432 }),
+		stats: Some(LibGroupMangaStats {
+			bookmarks: Some(LibGroupBookmarkStats {
+				count: 250000,
+				stats: vec![
+					bookmark(5, "Любимые", 20000),
+					bookmark(1, "Читаю", 150000),
+					bookmark(2, "", 80000),
+					bookmark(3, "Брошено", 0),
+				],
+			}),
+			likes: Some(LibGroupCount { count: 4321 }),
+		}),
+		..Default::default()
+	};
+
+	assert_eq!(
+		description(manga),
+		"★★★★✮ 9.46 (голосов: 123 456)<br/>\
+		Просмотры: 98 765 432 · Лайки: 4 321 · В закладках: 250 000<br/>\
+		Читаю: 150 000 · В планах: 80 000 · Любимые: 20 000\n\n\
+		Пираты.\n\n"
+	);
+}
+
+#[aidoku_test]
+fn stats_header_skips_missing_counters() {
+	let manga = LibGroupManga {
+		rus_name: "Без статистики".to_string(),
+		rating: Some(LibGroupRating {
+			average: "7".to_string(),
+			votes: 999,
+		}),
+		views: Some(LibGroupViews { total: 0 }),
+		..Default::default()
+	};
+
+	assert_eq!(description(manga), "★★★✮☆ 7.00 (голосов: 999)\n\n");
+}
+
+#[aidoku_test]
+fn no_header_without_rating_or_stats() {
+	let manga = LibGroupManga {
+		rus_name: "Пусто".to_string(),
+		summary: Some("Описание".to_string()),
+		..Default::default()
+	};
+
+	assert_eq!(description(manga), "Описание\n\n");
+}
+
+const RELATIONS_JSON: &str = r#"{"data":[
+	{"relation":{"id":1,"label":"Адаптация"},
+	 "media":{"id":1,"rus_name":"Поднятие уровня в одиночку","slug_url":"1--solo-leveling","site":3}},
+	{"relation":{"id":3,"label":""},
+	 "media":{"id":2,"rus_name":"","eng_name":"Solo Leveling: Ragnarok","slug_url":"2--ragnarok","site":1}},
+	{"relation":{"id":5,"label":"Спин-офф"},
+	 "media":{"id":3,"rus_name":"Другое","slug_url":"3--other","site":9}}
+]}"#;
+
+#[aidoku_test]
+fn relations_are_parsed() {
+	let relations = serde_json::from_str::<RelationsResponse>(RELATIONS_JSON)
+		.expect("valid relations json")
+		.data;
+
+	assert_eq!(relations.len(), 3);
+	assert_eq!(relations[0].relation.label, "Адаптация");
+	assert_eq!(relations[0].media.slug_url, "1--solo-leveling");
+	assert_eq!(relations[0].media.site, Some(3));
+	assert!(relations[1].relation.label.is_empty());
+	assert_eq!(
+		relations[1].media.eng_name.as_deref(),
+		Some("Solo Leveling: Ragnarok")
+	);
+}
+
+#[aidoku_test]
+fn relations_link_to_their_site() {
+	let relations = serde_json::from_str::<RelationsResponse>(RELATIONS_JSON)
+		.expect("valid relations json")
+		.data;
+	let manga = LibGroupManga {
+		rus_name: "Поднятие уровня в одиночку".to_string(),
+		slug_url: "7580--i-alone-level-up".to_string(),
+		relations: Some(relations),
+		..Default::default()
+	};
+
+	assert_eq!(
+		description(manga),
+		"[Связанное](https://mangalib.me/ru/manga/7580--i-alone-level-up?section=relations):<br/>\
+		Адаптация: [Поднятие уровня в одиночку](https://ranobelib.me/ru/book/1--solo-leveling)<br/>\
+		[Solo Leveling: Ragnarok](https://mangalib.me/ru/manga/2--ragnarok)<br/>\
+		Спин-офф: [Другое](https://mangalib.me/ru/manga/3--other)"
+	);
+}
+
+#[aidoku_test]
+fn collection_pages_are_cut_locally() {
+	let items: Vec<String> = (1..=45)
+		.map(|i| format!(r#"{{"media": {{"slug_url": "{i}--title"}}}}"#))
+		.collect();
+	let json = format!(
+		r#"{{"data": {{"id": 7, "name": "Любимое", "items": [{}]}}}}"#,
+		items.join(",")
+	);
+	let response = || serde_json::from_str::<CollectionResponse>(&json).expect("valid json");
+
+	let first = response().into_manga_page_result(1, &test_context());
+	assert_eq!(first.entries.len(), 30);
+	assert_eq!(first.entries[0].key, "1--title");
+	assert!(first.has_next_page);
+
+	let second = response().into_manga_page_result(2, &test_context());
+	assert_eq!(second.entries.len(), 15);
+	assert_eq!(second.entries[0].key, "31--title");
+	assert!(!second.has_next_page);
+
+	assert!(
+		response()
+			.into_manga_page_result(3, &test_context())
+			.entries
+			.is_empty()
+	);
+}
@@ -0,0 +1,141 @@
+use aidoku::{
+	Result,
+	alloc::{String, Vec, string::ToString},
+	imports::net::Request,
+	prelude::*,
+};
+
+use crate::{
+	auth::AuthRequest, context::Context, endpoints::Url, models::responses::PeopleSearchResponse,
+};
+
+/// Creators and teams whose catalogue can be browsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeopleKind {
+	Author,
+	Artist,
+	Publisher,
+	Team,
+	/// Author or artist, `/ru/people/` links don't tell which
+	Person,
+}
+
+impl PeopleKind {
+	/// Kinds with a text filter
+	pub const ALL: [Self; 4] = [Self::Author, Self::Artist, Self::Publisher, Self::Team];
+
+	/// Text filter id, also used as the listing id prefix (`author-<id>`)
+	pub const fn id(self) -> &'static str {
+		match self {
+			Self::Author => "author",
+			Self::Artist => "artist",
+			Self::Publisher => "publisher",
+			Self::Team => "team",
+			Self::Person => "people",
+		}
+	}
+
+	/// Listing name, the same as the filter title
+	pub const fn title(self) -> &'static str {
+		match self {
+			Self::Author => "Автор",
+			Self::Artist => "Художник",
+			Self::Publisher => "Издатель",
+			Self::Team => "Команда перевода",
+			Self::Person => "Автор и художник",
+		}
+	}
+
+	/// Catalog search parameters, a person's titles are searched once per role
+	pub const fn query_keys(self) -> &'static [&'static str] {
+		match self {
+			Self::Author => &["authors[]"],
+			Self::Artist => &["artists[]"],
+			Self::Publisher => &["publishers[]"],
+			Self::Team => &["teams[]"],
+			Self::Person => &["authors[]", "artists[]"],
+		}
+	}
+
+	/// API collection used to look up ids by name
+	const fn search_path(self) -> &'static str {
+		match self {
+			Self::Author | Self::Artist | Self::Person => "people",
+			Self::Publisher => "publishers",
+			Self::Team => "teams",
+		}
+	}
+
+	pub fn from_filter_id(id: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|kind| kind.id() == id)
+	}
+
+	/// Parse a listing id like `author-123` into its kind and id
+	pub fn from_listing_id(id: &str) -> Option<(Self, &str)> {
+		let (prefix, people_id) = id.split_once('-')?;
+		let kind = Self::from_filter_id(prefix)
+			.or_else(|| (prefix == Self::Person.id()).then_some(Self::Person))?;
+		(!people_id.is_empty() && people_id.bytes().all(|b| b.is_ascii_digit()))
+			.then_some((kind, people_id))
+	}
+}
+
+/// Listing name for a people page link, with the name from its slug if any,
+/// ex: `4583--oda-eiichiro` gives `Автор и художник: Oda Eiichiro`
+pub fn listing_name(kind: PeopleKind, slug: &str) -> String {
+	let name = slug
+		.split_once("--")
+		.map(|(_, name)| {
+			name.split('-')
+				.filter(|word| !word.is_empty())
+				.map(capitalize)
+				.collect::<Vec<String>>()
+				.join(" ")
+		})
+		.unwrap_or_default();
+
+	if name.is_empty() {
+		kind.title().into()
+	} else {
+		format!("{}: {name}", kind.title())
+	}
+}
+
+fn capitalize(word: &str) -> String {
+	let mut chars = word.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
+
+/// Resolve a name (or an id) entered in a text filter into an id
+pub fn resolve_id(ctx: &Context, kind: PeopleKind, value: &str) -> Result<Option<String>> {
+	let value = value.trim();
+	if value.is_empty() {
+		return Ok(None);
+	}
+	if value.parse::<i32>().is_ok() {
+		return Ok(Some(value.into()));
+	}
+
+	let search_url = Url::people_search(&ctx.api_url, kind.search_path(), value);
+	let people = Request::get(search_url)?
+		.authed(ctx)?
+		.get_json::<PeopleSearchResponse>()?
+		.data;
+
+	// Prefer an exact name match, then the most relevant result
+	let exact = people.iter().find(|person| {
+		person.name.eq_ignore_ascii_case(value)
+			|| person
+				.rus_name
+				.as_deref()
+				.is_some_and(|name| name.to_lowercase() == value.to_lowercase())
+	});
+
+	Ok(exact.or(people.first()).map(|person| person.id.to_string()))
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,68 @@
+use super::*;
+use aidoku_test::aidoku_test;
+
+#[aidoku_test]
+fn filter_ids() {
+	assert_eq!(
+		PeopleKind::from_filter_id("author"),
+		Some(PeopleKind::Author)
+	);
+	assert_eq!(PeopleKind::from_filter_id("team"), Some(PeopleKind::Team));
+	assert_eq!(PeopleKind::from_filter_id("genres"), None);
+	// People links cover both roles, there's no filter for that
+	assert_eq!(PeopleKind::from_filter_id("people"), None);
+}
+
+#[aidoku_test]
+fn listing_ids() {
+	assert_eq!(
+		PeopleKind::from_listing_id("artist-123"),
+		Some((PeopleKind::Artist, "123"))
+	);
+	assert_eq!(
+		PeopleKind::from_listing_id("publisher-7"),
+		Some((PeopleKind::Publisher, "7"))
+	);
+	assert_eq!(PeopleKind::from_listing_id("artist-"), None);
+	assert_eq!(PeopleKind::from_listing_id("team-abc"), None);
+	assert_eq!(
+		PeopleKind::from_listing_id("people-4583"),
+		Some((PeopleKind::Person, "4583"))
+	);
+	assert_eq!(PeopleKind::from_listing_id("popular"), None);
+}
+
+#[aidoku_test]
+fn people_search_both_roles() {
+	assert_eq!(PeopleKind::Person.query_keys(), ["authors[]", "artists[]"]);
+	assert_eq!(PeopleKind::Team.query_keys(), ["teams[]"]);
+}
+
+#[aidoku_test]
+fn listing_names_from_slugs() {
+	assert_eq!(
+		listing_name(PeopleKind::Person, "4583--oda-eiichiro"),
+		"Автор и художник: Oda Eiichiro"
+	);
+	assert_eq!(
+		listing_name(PeopleKind::Team, "12--some-team"),
+		"Команда перевода: Some Team"
+	);
+	assert_eq!(listing_name(PeopleKind::Publisher, "7"), "Издатель");
+}
+
+#[aidoku_test]
+fn numeric_values_skip_lookup() {
+	let ctx = Context {
+		api_url: "http://fake.api".to_string(),
+		base_url: "http://fake.base".to_string(),
+		site_id: 1,
+		cover_quality: "high".to_string(),
+	};
+
+	assert_eq!(
+		resolve_id(&ctx, PeopleKind::Author, " 42 ").ok().flatten(),
+		Some("42".to_string())
+	);
+	assert_eq!(resolve_id(&ctx, PeopleKind::Team, "").ok().flatten(), None);
+}
@@ -5,6 +5,13 @@ const BASE_URL_KEY: &str = "baseUrl";
 const API_URL_KEY: &str = "apiUrl";
 const IMAGE_SERVER_KEY: &str = "imageServerUrl";
 const COVER_QUALITY_KEY: &str = "coverQuality";
+const BRANCH_PREFERENCE_KEY: &str = "branchPreference";
+const PINNED_TEAM_KEY: &str = "pinnedTeam";
+const TRENDING_PERIOD_KEY: &str = "trendingPeriod";
+const TRENDING_POPULARITY_KEY: &str = "trendingPopularity";
+const CHAPTER_COMMENTS_KEY: &str = "chapterComments";
+const HIDE_LOCKED_KEY: &str = "hideLocked";
+const SYNC_PROGRESS_KEY: &str = "syncProgress";
 
 const DEFAULT_API_URL: &str = "https://api.imglib.info";
 const DEFAULT_IMAGE_SERVER: &str = "compress";
@@ -29,3 +36,87 @@ pub fn get_image_server_url() -> String {
 pub fn get_cover_quality_url() -> String {
 	defaults_get::<String>(COVER_QUALITY_KEY).unwrap_or_else(|| DEFAULT_COVER_QUALITY.into())
 }
+
+/// Whether team notes and top comments are shown after the last page of a chapter
+pub fn get_chapter_comments_enabled() -> bool {
+	defaults_get::<bool>(CHAPTER_COMMENTS_KEY).unwrap_or(false)
+}
+
+/// Whether paid, early access and moderated chapters are left out of the chapter list
+pub fn get_hide_locked_chapters() -> bool {
+	defaults_get::<bool>(HIDE_LOCKED_KEY).unwrap_or(false)
+}
+
+/// Whether opened chapters are sent to the site's reading history
+pub fn get_sync_progress_enabled() -> bool {
+	defaults_get::<bool>(SYNC_PROGRESS_KEY).unwrap_or(true)
+}
+
+/// How chapters translated by several teams are shown
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BranchPreference {
+	/// Show every branch
+	All,
+	/// Show only the branch of the pinned team (by name or id), falling back to the newest one
+	Pinned(String),
+	/// Show only the newest readable branch
+	Newest,
+}
+
+/// Get the branch (translation team) preference
+pub fn get_branch_preference() -> BranchPreference {
+	match defaults_get::<String>(BRANCH_PREFERENCE_KEY).as_deref() {
+		Some("pinned") => BranchPreference::Pinned(
+			defaults_get::<String>(PINNED_TEAM_KEY)
+				.map(|team| team.trim().into())
+				.unwrap_or_default(),
+		),
+		Some("newest") => BranchPreference::Newest,
+		_ => BranchPreference::All,
+	}
+}
+
+/// Period used by the "Сейчас читают" section
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrendingPeriod {
+	Day,
+	Week,
+	Month,
+	All,
+}
+
+impl TrendingPeriod {
+	/// Value of the `time` parameter of `/api/media/top-views`
+	pub const fn as_param(self) -> &'static str {
+		match self {
+			Self::Day => "day",
+			Self::Week => "week",
+			Self::Month => "month",
+			Self::All => "all",
+		}
+	}
+
+	pub const fn title(self) -> &'static str {
+		match self {
+			Self::Day => "за день",
+			Self::Week => "за неделю",
+			Self::Month => "за месяц",
+			Self::All => "за всё время",
+		}
+	}
+}
+
+/// Get the "Сейчас читают" period
+pub fn get_trending_period() -> TrendingPeriod {
+	match defaults_get::<String>(TRENDING_PERIOD_KEY).as_deref() {
+		Some("week") => TrendingPeriod::Week,
+		Some("month") => TrendingPeriod::Month,
+		Some("all") => TrendingPeriod::All,
+		_ => TrendingPeriod::Day,
+	}
+}
+
+/// Whether "Сейчас читают" only shows new titles ("Новинки") instead of all titles
+pub fn get_trending_new_only() -> bool {
+	defaults_get::<String>(TRENDING_POPULARITY_KEY).as_deref() != Some("all")
+}